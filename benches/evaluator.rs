@@ -0,0 +1,61 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use joker_poker::lookup::{self, JOKER};
+use joker_poker::Hand;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+
+// A fixed sample of hands dealt from a deck with two jokers
+fn sample_hands(count: usize, size: usize) -> Vec<Vec<u8>> {
+    let mut rng = StdRng::seed_from_u64(7);
+    let mut deck: Vec<u8> = (0..52).chain([JOKER, JOKER]).collect();
+    (0..count)
+        .map(|_| {
+            deck.shuffle(&mut rng);
+            deck[..size].to_vec()
+        })
+        .collect()
+}
+
+fn bench_evaluators(c: &mut Criterion) {
+    let codes = sample_hands(1000, 5);
+    let hands: Vec<Hand> = codes.iter()
+        .map(|hand| Hand::new(hand.iter().map(|&code| lookup::decode(code)).collect()))
+        .collect();
+
+    let mut group = c.benchmark_group("evaluate_1000_hands");
+    group.bench_function("hand_evaluation", |b| {
+        b.iter(|| {
+            for hand in &hands {
+                black_box(hand.evaluation().value);
+            }
+        })
+    });
+    group.bench_function("lookup_packed", |b| {
+        b.iter(|| {
+            for hand in &codes {
+                black_box(lookup::evaluate_codes(black_box(hand)));
+            }
+        })
+    });
+    group.bench_function("lookup_hand_value", |b| {
+        b.iter(|| {
+            for hand in &hands {
+                black_box(hand.value());
+            }
+        })
+    });
+    group.finish();
+
+    let seven = sample_hands(100, 7);
+    c.bench_function("lookup_best_of_seven_100_hands", |b| {
+        b.iter(|| {
+            for hand in &seven {
+                black_box(lookup::evaluate_codes(black_box(hand)));
+            }
+        })
+    });
+}
+
+criterion_group!(benches, bench_evaluators);
+criterion_main!(benches);
@@ -0,0 +1,13 @@
+// A player's decision during a betting round. `Raise` carries the total that
+// the player's bet for the round is raised to, not the size of the increment.
+// `AllIn` commits every remaining chip, whether or not that covers the bet.
+// `Draw` lists the 0-based positions to discard during the draw.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Action {
+    Fold,
+    Check,
+    Call,
+    Raise(u32),
+    AllIn,
+    Draw(Vec<usize>),
+}
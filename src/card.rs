@@ -1,6 +1,11 @@
+use std::fmt;
 use std::hash::Hash;
+use std::str::FromStr;
+use crate::card_set::MAX_JOKERS;
+use crate::error::GameError;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Suit {
     Hearts,
     Diamonds,
@@ -8,13 +13,55 @@ pub enum Suit {
     Spades,
 }
 
+impl Suit {
+    pub const ALL: [Suit; 4] = [Suit::Hearts, Suit::Diamonds, Suit::Clubs, Suit::Spades];
+
+    // Lower-case letter used in card notation, e.g. the 'h' of "Ah"
+    pub fn letter(self) -> char {
+        match self {
+            Suit::Hearts => 'h',
+            Suit::Diamonds => 'd',
+            Suit::Clubs => 'c',
+            Suit::Spades => 's',
+        }
+    }
+
+    // Accepts the letter in either case or the symbol
+    pub fn from_char(c: char) -> Option<Suit> {
+        match c {
+            'h' | 'H' | '♥' | '♡' => Some(Suit::Hearts),
+            'd' | 'D' | '♦' | '♢' => Some(Suit::Diamonds),
+            'c' | 'C' | '♣' | '♧' => Some(Suit::Clubs),
+            's' | 'S' | '♠' | '♤' => Some(Suit::Spades),
+            _ => None,
+        }
+    }
+
+    pub fn symbol(self) -> char {
+        match self {
+            Suit::Hearts => '♥',
+            Suit::Diamonds => '♦',
+            Suit::Clubs => '♣',
+            Suit::Spades => '♠',
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Rank {
     Two, Three, Four, Five, Six, Seven, Eight, Nine, Ten,
     Jack, Queen, King, Ace,
 }
 
 impl Rank {
+    // Lowest to highest
+    pub const ALL: [Rank; 13] = [
+        Rank::Two, Rank::Three, Rank::Four, Rank::Five, Rank::Six,
+        Rank::Seven, Rank::Eight, Rank::Nine, Rank::Ten,
+        Rank::Jack, Rank::Queen, Rank::King, Rank::Ace,
+    ];
+
     pub fn from_u8(value: u8) -> Option<Rank> {
         match value {
             0 => Some(Rank::Two),
@@ -34,7 +81,7 @@ impl Rank {
         }
     }
 
-    pub fn to_u8(&self) -> u8 {
+    pub fn to_u8(self) -> u8 {
         match self {
             Rank::Two => 0,
             Rank::Three => 1,
@@ -51,24 +98,171 @@ impl Rank {
             Rank::Ace => 12,
         }
     }
+
+    // Inverse of `symbol`; lower-case letters are accepted too
+    pub fn from_char(c: char) -> Option<Rank> {
+        Rank::ALL.into_iter().find(|rank| rank.symbol() == c.to_ascii_uppercase())
+    }
+
+    pub fn symbol(self) -> char {
+        match self {
+            Rank::Two => '2',
+            Rank::Three => '3',
+            Rank::Four => '4',
+            Rank::Five => '5',
+            Rank::Six => '6',
+            Rank::Seven => '7',
+            Rank::Eight => '8',
+            Rank::Nine => '9',
+            Rank::Ten => 'T',
+            Rank::Jack => 'J',
+            Rank::Queen => 'Q',
+            Rank::King => 'K',
+            Rank::Ace => 'A',
+        }
+    }
 }
 
-#[derive(Clone, Copy)]
-pub struct Card {
-    pub rank: Rank,
-    pub suit: Suit,
-    pub is_joker: bool,
+// Tells the jokers in a deck apart; the first joker is 0
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct JokerId(pub u8);
+
+// A joker has no rank or suit of its own. Cards order by rank, then suit,
+// with every joker after the naturals.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Card {
+    Natural { rank: Rank, suit: Suit },
+    Joker(JokerId),
 }
 
 impl Card {
-    pub fn new(rank: Rank, suit: Suit, is_joker: bool) -> Self {
-        Card { rank, suit, is_joker }
+    pub fn new(rank: Rank, suit: Suit) -> Self {
+        Card::Natural { rank, suit }
+    }
+
+    pub fn joker(id: u8) -> Self {
+        Card::Joker(JokerId(id))
+    }
+
+    pub fn is_joker(&self) -> bool {
+        matches!(self, Card::Joker(_))
+    }
+
+    pub fn rank(&self) -> Option<Rank> {
+        self.natural().map(|(rank, _)| rank)
+    }
+
+    pub fn suit(&self) -> Option<Suit> {
+        self.natural().map(|(_, suit)| suit)
+    }
+
+    pub fn natural(&self) -> Option<(Rank, Suit)> {
+        match *self {
+            Card::Natural { rank, suit } => Some((rank, suit)),
+            Card::Joker(_) => None,
+        }
+    }
+
+    // Compact index: naturals are `rank * 4 + suit` (0..52) and joker n is
+    // 52 + n
+    pub fn index(self) -> u8 {
+        match self {
+            Card::Natural { rank, suit } => {
+                let suit = Suit::ALL.iter().position(|&s| s == suit).unwrap_or(0) as u8;
+                rank.to_u8() * 4 + suit
+            }
+            Card::Joker(JokerId(id)) => 52u8.saturating_add(id),
+        }
+    }
+
+    pub fn from_index(index: u8) -> Option<Card> {
+        if index >= 52 {
+            return Some(Card::joker(index - 52));
+        }
+        let rank = Rank::from_u8(index / 4)?;
+        Some(Card::new(rank, Suit::ALL[(index % 4) as usize]))
     }
 }
 
 impl std::fmt::Debug for Card {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let joker_str = if self.is_joker { " (Joker)" } else { "" };
-        write!(f, "{:?} of {:?}{}", self.rank, self.suit, joker_str)
+        match self {
+            Card::Natural { rank, suit } => write!(f, "{:?} of {:?}", rank, suit),
+            Card::Joker(JokerId(id)) => write!(f, "Joker #{}", id),
+        }
+    }
+}
+
+// Standard notation: rank then suit, as in "Ah", "Td" or "9s", with "Jk" for
+// a joker. The alternate form `{:#}` uses suit symbols instead ("A♥").
+impl fmt::Display for Card {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (rank, suit) = match *self {
+            Card::Natural { rank, suit } => (rank, suit),
+            // Joker 0 is plain "Jk"; the others carry their id so they parse back
+            Card::Joker(JokerId(0)) => return write!(f, "Jk"),
+            Card::Joker(JokerId(id)) => return write!(f, "Jk{}", id),
+        };
+        let suit = if f.alternate() { suit.symbol() } else { suit.letter() };
+        write!(f, "{}{}", rank.symbol(), suit)
+    }
+}
+
+impl FromStr for Card {
+    type Err = GameError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_card(s).map_err(GameError::InvalidCardNotation)
+    }
+}
+
+// Cards serialize as their notation. A joker other than joker 0 carries its
+// number ("Jk1") so that it reads back as the same card.
+#[cfg(feature = "serde")]
+impl serde::Serialize for Card {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Card {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let notation = String::deserialize(deserializer)?;
+        parse_card(&notation).map_err(serde::de::Error::custom)
     }
-}
\ No newline at end of file
+}
+
+// Parses one card, describing what is wrong with it on failure. "Jk" is
+// joker 0 and "Jk2" joker 2; `Hand` numbers a hand's plain "Jk"s itself.
+pub(crate) fn parse_card(s: &str) -> Result<Card, String> {
+    let s = s.trim();
+    if s.eq_ignore_ascii_case("jk") {
+        return Ok(Card::joker(0));
+    }
+    if let Some(id) = s.get(..2).filter(|jk| jk.eq_ignore_ascii_case("jk")).and(s.get(2..)) {
+        return id.parse::<u8>().ok()
+            .filter(|&id| u32::from(id) < MAX_JOKERS)
+            .map(Card::joker)
+            .ok_or_else(|| format!("unknown joker \"{}\" (use Jk or Jk0 to Jk{})", s, MAX_JOKERS - 1));
+    }
+    let mut chars = s.chars();
+    let Some(suit_char) = chars.next_back() else {
+        return Err("empty card".to_string());
+    };
+    let rank_part = chars.as_str();
+    if rank_part.is_empty() {
+        return Err(format!("\"{}\" needs a rank and a suit, e.g. \"Ah\"", s));
+    }
+
+    let mut rank_chars = rank_part.chars();
+    let rank = match (rank_chars.next(), rank_chars.next()) {
+        _ if rank_part == "10" => Some(Rank::Ten),
+        (Some(c), None) => Rank::from_char(c),
+        _ => None,
+    }
+    .ok_or_else(|| format!("unknown rank \"{}\" in \"{}\" (use 2-9, T, J, Q, K or A)", rank_part, s))?;
+    let suit = Suit::from_char(suit_char)
+        .ok_or_else(|| format!("unknown suit '{}' in \"{}\" (use h, d, c or s)", suit_char, s))?;
+    Ok(Card::new(rank, suit))
+}
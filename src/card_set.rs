@@ -0,0 +1,199 @@
+use std::fmt;
+use std::ops::{BitAnd, BitOr, Sub};
+use rand::seq::IteratorRandom;
+use rand::Rng;
+use crate::card::Card;
+use crate::hand::Hand;
+
+// Jokers 0 to 11 fill the bits above the 52 naturals
+pub const MAX_JOKERS: u32 = 12;
+
+// A set of distinct cards as a bitmask, bit `Card::index` for each card.
+// Iteration runs in index order: naturals by rank then suit, then jokers.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CardSet(u64);
+
+impl CardSet {
+    pub fn new() -> Self {
+        CardSet(0)
+    }
+
+    // Every natural card plus jokers 0 to `num_jokers - 1`
+    pub fn full(num_jokers: u32) -> Self {
+        assert!(num_jokers <= MAX_JOKERS, "a CardSet holds at most {} jokers", MAX_JOKERS);
+        CardSet(u64::MAX >> (MAX_JOKERS - num_jokers))
+    }
+
+    pub fn from_bits(bits: u64) -> Self {
+        CardSet(bits)
+    }
+
+    pub fn bits(self) -> u64 {
+        self.0
+    }
+
+    pub fn len(self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    // A joker past MAX_JOKERS is never in a set
+    pub fn contains(self, card: Card) -> bool {
+        bit(card).is_some_and(|bit| self.0 & bit != 0)
+    }
+
+    // Returns false if the card was already in the set. Panics for a joker
+    // past MAX_JOKERS, which no set can hold.
+    pub fn insert(&mut self, card: Card) -> bool {
+        let bit = bit(card).unwrap_or_else(|| panic!("{:?} does not fit in a CardSet", card));
+        let present = self.0 & bit != 0;
+        self.0 |= bit;
+        !present
+    }
+
+    // Returns false if the card was not in the set
+    pub fn remove(&mut self, card: Card) -> bool {
+        let present = self.contains(card);
+        if let Some(bit) = bit(card) {
+            self.0 &= !bit;
+        }
+        present
+    }
+
+    pub fn union(self, other: CardSet) -> CardSet {
+        CardSet(self.0 | other.0)
+    }
+
+    pub fn intersection(self, other: CardSet) -> CardSet {
+        CardSet(self.0 & other.0)
+    }
+
+    pub fn difference(self, other: CardSet) -> CardSet {
+        CardSet(self.0 & !other.0)
+    }
+
+    pub fn iter(self) -> CardSetIter {
+        CardSetIter(self.0)
+    }
+
+    // `count` different cards chosen uniformly at random, or the whole set
+    // if it is smaller than that
+    pub fn sample<R: Rng + ?Sized>(self, rng: &mut R, count: usize) -> CardSet {
+        self.iter().choose_multiple(rng, count).into_iter().collect()
+    }
+}
+
+// None for a joker numbered past MAX_JOKERS
+fn bit(card: Card) -> Option<u64> {
+    let index = card.index();
+    (index < 64).then(|| 1 << index)
+}
+
+pub struct CardSetIter(u64);
+
+impl Iterator for CardSetIter {
+    type Item = Card;
+
+    fn next(&mut self) -> Option<Card> {
+        if self.0 == 0 {
+            return None;
+        }
+        let index = self.0.trailing_zeros() as u8;
+        self.0 &= self.0 - 1;
+        Card::from_index(index)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.0.count_ones() as usize;
+        (len, Some(len))
+    }
+}
+
+impl ExactSizeIterator for CardSetIter {}
+
+impl IntoIterator for CardSet {
+    type Item = Card;
+    type IntoIter = CardSetIter;
+
+    fn into_iter(self) -> CardSetIter {
+        self.iter()
+    }
+}
+
+impl FromIterator<Card> for CardSet {
+    fn from_iter<I: IntoIterator<Item = Card>>(cards: I) -> Self {
+        let mut set = CardSet::new();
+        set.extend(cards);
+        set
+    }
+}
+
+impl Extend<Card> for CardSet {
+    fn extend<I: IntoIterator<Item = Card>>(&mut self, cards: I) {
+        for card in cards {
+            self.insert(card);
+        }
+    }
+}
+
+impl From<Card> for CardSet {
+    fn from(card: Card) -> Self {
+        let mut set = CardSet::new();
+        set.insert(card);
+        set
+    }
+}
+
+impl From<&Hand> for CardSet {
+    fn from(hand: &Hand) -> Self {
+        hand.cards.iter().copied().collect()
+    }
+}
+
+// The hand holds the cards in index order
+impl From<CardSet> for Hand {
+    fn from(set: CardSet) -> Self {
+        Hand::new(set.iter().collect())
+    }
+}
+
+impl BitOr for CardSet {
+    type Output = CardSet;
+
+    fn bitor(self, other: CardSet) -> CardSet {
+        self.union(other)
+    }
+}
+
+impl BitAnd for CardSet {
+    type Output = CardSet;
+
+    fn bitand(self, other: CardSet) -> CardSet {
+        self.intersection(other)
+    }
+}
+
+impl Sub for CardSet {
+    type Output = CardSet;
+
+    fn sub(self, other: CardSet) -> CardSet {
+        self.difference(other)
+    }
+}
+
+impl fmt::Debug for CardSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+// Same notation as a hand, e.g. "2c Ah Jk"
+impl fmt::Display for CardSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&Hand::from(*self), f)
+    }
+}
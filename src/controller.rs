@@ -0,0 +1,39 @@
+use crate::action::Action;
+use crate::card::Card;
+use crate::game::GameState;
+use crate::hand::Hand;
+
+// What one player may see of the table when it is their turn: their own hand
+// and the public state of every seat.
+#[derive(Debug, Clone)]
+pub struct TableView<'a> {
+    pub player: usize,
+    pub state: GameState,
+    pub hand: Option<&'a Hand>,
+    pub board: &'a [Card],  // Shared cards, empty in games without a board
+    pub seats: Vec<SeatView<'a>>,
+    pub dealer: usize,  // Seat holding the button
+    pub pot: u32,
+    pub current_bet: u32,
+    pub to_call: u32,
+    pub min_raise: u32,
+    pub max_bet: u32,  // All-in, or less when the pot limits the bet
+}
+
+#[derive(Debug, Clone)]
+pub struct SeatView<'a> {
+    pub name: &'a str,
+    pub chips: u32,
+    pub in_round: bool,
+    pub street_bet: u32,
+    pub up_cards: Vec<Card>,  // Face-up cards of a player still in the round
+}
+
+// Makes the decisions for one seat. A rejected choice is reported through the
+// event sink and the controller is asked again.
+pub trait PlayerController {
+    fn choose_action(&mut self, view: &TableView) -> Action;
+
+    // 0-based positions of the cards to throw away; empty to stand pat
+    fn choose_discards(&mut self, view: &TableView) -> Vec<usize>;
+}
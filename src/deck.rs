@@ -1,41 +1,154 @@
 use rand::seq::SliceRandom;
-use crate::card::{Card, Suit, Rank};
+use rand::SeedableRng;
+use rand_chacha::ChaCha12Rng;
+use crate::card::{Card, JokerId, Suit, Rank};
+use crate::card_set::{CardSet, MAX_JOKERS};
+use crate::error::{GameError, GameResult};
 use crate::hand::Hand;
 
+// The generator behind rand's StdRng, so a seed shuffles the same either way.
+// Unlike StdRng its state can be saved with the rest of the game.
+pub type DeckRng = ChaCha12Rng;
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Deck {
     cards: Vec<Card>,
+    remaining: CardSet,   // The same cards as `cards`, for lookups
+    discards: Vec<Card>,  // Mucked and drawn-away cards, face down
+    rng: DeckRng,         // Drives every shuffle of this deck
 }
 
 impl Deck {
     pub fn new(num_jokers: u32) -> Self {
+        Self::with_rng(num_jokers, DeckRng::from_entropy())
+    }
+
+    // The same seed always shuffles into the same order
+    pub fn with_seed(num_jokers: u32, seed: u64) -> Self {
+        Self::with_rng(num_jokers, DeckRng::seed_from_u64(seed))
+    }
+
+    // Panics if `num_jokers` is over MAX_JOKERS
+    pub fn with_rng(num_jokers: u32, rng: DeckRng) -> Self {
+        assert!(num_jokers <= MAX_JOKERS, "a deck holds at most {} jokers", MAX_JOKERS);
         let mut cards = Vec::new();
         
         // Add standard cards
-        for &suit in &[Suit::Hearts, Suit::Diamonds, Suit::Clubs, Suit::Spades] {
-            for rank in [
-                Rank::Two, Rank::Three, Rank::Four, Rank::Five, Rank::Six,
-                Rank::Seven, Rank::Eight, Rank::Nine, Rank::Ten,
-                Rank::Jack, Rank::Queen, Rank::King, Rank::Ace,
-            ].iter() {
-                cards.push(Card::new(*rank, suit, false));
+        for suit in Suit::ALL {
+            for rank in Rank::ALL {
+                cards.push(Card::new(rank, suit));
             }
         }
         
         // Add specified number of jokers
-        for _ in 0..num_jokers {
-            cards.push(Card::new(Rank::Ace, Suit::Hearts, true));
+        for id in 0..num_jokers {
+            cards.push(Card::joker(id as u8));
         }
         
-        Deck { cards }
+        Deck { remaining: cards.iter().copied().collect(), cards, discards: Vec::new(), rng }
     }
     
+    // A deck in exactly the given order, the first card dealt first. It must
+    // be a full deck: every natural card once plus jokers 0 to
+    // `num_jokers - 1`.
+    pub fn from_cards(cards: Vec<Card>, num_jokers: u32) -> GameResult<Self> {
+        let jokers = cards.iter().filter(|c| c.is_joker()).count() as u32;
+        let out_of_range = cards.iter().any(|c| matches!(c, Card::Joker(JokerId(id)) if *id as u32 >= num_jokers));
+        if jokers != num_jokers || out_of_range || num_jokers > MAX_JOKERS {
+            return Err(GameError::InvalidJokerCount(jokers));
+        }
+        let mut remaining = CardSet::new();
+        for card in &cards {
+            if !remaining.insert(*card) {
+                return Err(GameError::DuplicateCard(format!("{:?}", card)));
+            }
+        }
+        if cards.len() != 52 + num_jokers as usize {
+            return Err(GameError::InvalidCardCount(cards.len()));
+        }
+        Ok(Deck { cards, remaining, discards: Vec::new(), rng: DeckRng::from_entropy() })
+    }
+
+    // Puts `top` on top of an unshuffled deck, for setting up a scenario
+    // without spelling out all 52 cards. A game deals it in the order of its
+    // variant's steps: each deal goes round the players in seat order, so in
+    // joker draw the first five cards go to the first player, while in
+    // Hold'em the first two do and the board follows the hole cards.
+    pub fn stacked(top: Vec<Card>, num_jokers: u32) -> GameResult<Self> {
+        if num_jokers > MAX_JOKERS {
+            return Err(GameError::InvalidJokerCount(num_jokers));
+        }
+        let mut rest = Deck::new(num_jokers).cards;
+        for card in &top {
+            match rest.iter().position(|c| c == card) {
+                Some(i) => {
+                    rest.remove(i);
+                }
+                // A joker missing from a fresh deck is numbered past its jokers
+                None if card.is_joker() && top.iter().filter(|&c| c == card).count() == 1 => {
+                    let jokers = top.iter().filter(|c| c.is_joker()).count() as u32;
+                    return Err(GameError::InvalidJokerCount(jokers));
+                }
+                None => return Err(GameError::DuplicateCard(format!("{:?}", card))),
+            }
+        }
+        let mut cards = top;
+        cards.extend(rest);
+        Self::from_cards(cards, num_jokers)
+    }
+
+    // Restarts the generator behind later shuffles, such as reshuffling the
+    // discards, without touching the order of the stub
+    pub fn reseed(&mut self, seed: u64) {
+        self.rng = DeckRng::seed_from_u64(seed);
+    }
+
     pub fn shuffle(&mut self) {
-        let mut rng = rand::thread_rng();
-        self.cards.shuffle(&mut rng);
+        self.cards.shuffle(&mut self.rng);
     }
     
-    pub fn deal(&mut self, num_cards: usize) -> Hand {
+    pub fn len(&self) -> usize {
+        self.cards.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cards.is_empty()
+    }
+
+    // The cards still in the stub, in no particular order
+    pub fn remaining(&self) -> CardSet {
+        self.remaining
+    }
+
+    pub fn contains(&self, card: Card) -> bool {
+        self.remaining.contains(card)
+    }
+
+    // Deals from the top of the stub. Nothing is dealt if the stub is short.
+    pub fn deal(&mut self, num_cards: usize) -> GameResult<Hand> {
+        if num_cards > self.cards.len() {
+            return Err(GameError::EmptyDeck);
+        }
         let cards: Vec<Card> = self.cards.drain(0..num_cards).collect();
-        Hand::new(cards)
+        for &card in &cards {
+            self.remaining.remove(card);
+        }
+        Ok(Hand::new(cards))
+    }
+
+    pub fn discard(&mut self, cards: Vec<Card>) {
+        self.discards.extend(cards);
+    }
+
+    pub fn discards_len(&self) -> usize {
+        self.discards.len()
+    }
+
+    // Shuffles the discard pile and puts it under the stub, so what is left
+    // of the stub is still dealt first. Live cards never reach the pile.
+    pub fn reshuffle_discards(&mut self) {
+        self.discards.shuffle(&mut self.rng);
+        self.remaining.extend(self.discards.iter().copied());
+        self.cards.append(&mut self.discards);
     }
-}
\ No newline at end of file
+}
@@ -1,5 +1,5 @@
 use thiserror::Error;
-use std::fmt;
+use crate::game::GameState;
 
 #[derive(Error, Debug)]
 pub enum GameError {
@@ -27,6 +27,12 @@ pub enum GameError {
     #[error("Invalid number of cards: {0}")]
     InvalidCardCount(usize),
 
+    #[error("Invalid card notation: {0}")]
+    InvalidCardNotation(String),
+
+    #[error("Duplicate card: {0}")]
+    DuplicateCard(String),
+
     #[error("Card not found in hand: {0}")]
     CardNotFound(String),
 
@@ -61,6 +67,15 @@ pub enum GameError {
         attempted: u32,
     },
 
+    #[error("Invalid save file: {0}")]
+    InvalidSaveFile(String),
+
+    #[error("Unsupported save file version {found}, expected {supported}")]
+    UnsupportedSaveVersion {
+        found: u32,
+        supported: u32,
+    },
+
     #[error("IO error: {0}")]
     IoError(#[from] std::io::Error),
 }
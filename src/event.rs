@@ -0,0 +1,54 @@
+use crate::card::Card;
+use crate::hand::HandEvaluation;
+
+// The compulsory bets posted before the cards are played
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ForcedBet {
+    Ante,
+    SmallBlind,
+    BigBlind,
+    BringIn,
+}
+
+// Everything that happens at the table, in order. The engine reports through
+// an EventSink instead of printing, so a terminal, a server or a test can
+// each present the round however it likes. Players are identified by seat.
+#[derive(Debug, Clone)]
+pub enum GameEvent {
+    // The seed the round's deck was shuffled with, for replaying it, and
+    // the seat holding the button
+    RoundStarted { seed: u64, dealer: usize },
+    // `all_in` is set when the player could not cover the full amount
+    Posted { player: usize, bet: ForcedBet, amount: u32, all_in: bool },
+    // Shared cards turned up on the board
+    BoardDealt { cards: Vec<Card> },
+    // Cards dealt face up to one player
+    UpCardsDealt { player: usize, cards: Vec<Card> },
+    BettingStarted { final_round: bool },
+    Checked { player: usize },
+    Called { player: usize, amount: u32 },
+    Raised { player: usize, to: u32 },
+    Folded { player: usize },
+    AllIn { player: usize, amount: u32 },
+    DrawingStarted,
+    Drew { player: usize, count: usize },
+    // A controller's choice was refused; the player is asked again
+    ActionRejected { player: usize, reason: String },
+    ShowdownStarted,
+    HandShown { player: usize, cards: Vec<Card>, evaluation: HandEvaluation },
+    // `pot` is 0 for the main pot and 1.. for the side pots
+    PotAwarded { pot: usize, player: usize, amount: u32 },
+    RoundOver,
+}
+
+pub trait EventSink {
+    fn emit(&mut self, event: GameEvent);
+}
+
+// Collecting into a Vec is handy for tests and for callers that want to
+// inspect a round after the fact.
+impl EventSink for Vec<GameEvent> {
+    fn emit(&mut self, event: GameEvent) {
+        self.push(event);
+    }
+}
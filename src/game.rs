@@ -1,16 +1,57 @@
-use std::io::{self, Write};
+use crate::action::Action;
+use crate::card::Card;
+use crate::card_set::MAX_JOKERS;
+use crate::controller::{PlayerController, SeatView, TableView};
 use crate::deck::Deck;
+use crate::error::{GameError, GameResult};
+use crate::event::{EventSink, ForcedBet, GameEvent};
 use crate::player::Player;
-use crate::hand::{Hand,HandRank};
+use crate::hand::{combinations, Hand, HandValue};
+use crate::variant::{BetLimit, JokerDraw, Step, Variant};
+#[cfg(feature = "serde")]
+use std::path::Path;
 
-#[derive(Debug, PartialEq, Eq)]
+pub use crate::variant::{MAX_DRAW, MAX_DRAW_WITH_ACE};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum GameState {
     Betting,
     Drawing,
+    FinalBetting,  // The variant's last betting round
     ShowDown,
     GameOver,
 }
 
+// A share of the chips in the middle together with the players who can win
+// it. The first pot is the main pot; any others are side pots created when a
+// player went all-in for less than the others put in.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pot {
+    pub amount: u32,
+    pub eligible: Vec<usize>,
+}
+
+// The bets posted before a round's first betting round. Blinds are posted
+// by the two seats after the button, heads-up by the button and the other
+// player; without blinds, the first seat to act posts the bring-in. With
+// neither, the first betting round opens at the minimum bet instead.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ForcedBets {
+    pub ante: u32,
+    pub small_blind: u32,
+    pub big_blind: u32,
+    pub bring_in: u32,
+}
+
+// Spreads consecutive round numbers across the seed space
+const ROUND_SEED_MIX: u64 = 0x9E37_79B9_7F4A_7C15;
+
+// Bumped whenever the saved form of Game changes
+pub const SAVE_VERSION: u32 = 6;
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Game {
     deck: Deck,
     players: Vec<Player>,
@@ -19,16 +60,58 @@ pub struct Game {
     state: GameState,
     min_bet: u32,
     num_jokers: u32,  // Add this field
+    current_bet: u32,      // Amount to match in the current betting round
+    last_raise: u32,       // Size of the last full raise this betting round
+    street_bets: Vec<u32>, // What each player has put in this betting round
+    to_act: Vec<bool>,     // Players who still owe an action this round
+    may_raise: Vec<bool>,  // Cleared when a short all-in only asks for a call
+    round_bets: Vec<u32>,  // What each player has put in over the whole hand
+    round_active: bool,    // Cards are dealt and the pot not yet awarded
+    reshuffle_discards: bool,  // Refill a short stub from the discard pile
+    seed: u64,             // Every round's shuffle is derived from this
+    rounds_dealt: u64,
+    round_seed: u64,       // Seed of the current round's deck
+    stacked_deck: Option<Deck>,  // Dealt as is by the next round
+    dealer: usize,         // Seat holding the dealer button
+    forced_bets: ForcedBets,
+    #[cfg_attr(feature = "serde", serde(with = "crate::variant::serde_by_name"))]
+    variant: Box<dyn Variant>,
+    step: usize,           // Index into the variant's steps
+    board: Vec<Card>,      // Shared cards dealt so far this round
+}
+
+// What `Game::save` writes: the format version, then the game itself
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize)]
+struct SaveFile<'a> {
+    version: u32,
+    game: &'a Game,
+}
+
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct SaveHeader {
+    version: u32,
+}
+
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct LoadedSave {
+    game: Game,
 }
 
 impl Game {
-    pub fn new(player_names: Vec<String>, starting_chips: u32, min_bet: u32, num_jokers: u32) -> Self {
-        let players = player_names
+    pub fn new(player_names: Vec<String>, starting_chips: u32, min_bet: u32, num_jokers: u32) -> GameResult<Self> {
+        if num_jokers > MAX_JOKERS {
+            return Err(GameError::InvalidJokerCount(num_jokers));
+        }
+        let players: Vec<Player> = player_names
             .into_iter()
             .map(|name| Player::new(name, starting_chips))
             .collect();
+        let num_players = players.len();
 
-        Game {
+        Ok(Game {
             deck: Deck::new(num_jokers),
             players,
             pot: 0,
@@ -36,7 +119,69 @@ impl Game {
             state: GameState::Betting,
             min_bet,
             num_jokers,
+            current_bet: 0,
+            last_raise: min_bet,
+            street_bets: vec![0; num_players],
+            to_act: vec![false; num_players],
+            may_raise: vec![true; num_players],
+            round_bets: vec![0; num_players],
+            round_active: false,
+            reshuffle_discards: false,
+            seed: rand::random(),
+            rounds_dealt: 0,
+            round_seed: 0,
+            stacked_deck: None,
+            // The button starts on the last seat so that seat 0 acts first
+            dealer: num_players.saturating_sub(1),
+            forced_bets: ForcedBets::default(),
+            variant: Box::new(JokerDraw),
+            step: 0,
+            board: Vec::new(),
+        })
+    }
+
+    // Writes the whole game to `path`, including a round in progress, the
+    // deck's shuffle state and the seed that later rounds are dealt from.
+    // The file is written next to `path` first and then renamed over it, so
+    // a crash part way through leaves the previous save intact.
+    #[cfg(feature = "serde")]
+    pub fn save(&self, path: impl AsRef<Path>) -> GameResult<()> {
+        let file = SaveFile { version: SAVE_VERSION, game: self };
+        let json = serde_json::to_string(&file).map_err(|err| GameError::InvalidSaveFile(err.to_string()))?;
+        let path = path.as_ref();
+        let mut temp = path.as_os_str().to_owned();
+        temp.push(".tmp");
+        std::fs::write(&temp, json)?;
+        if let Err(err) = std::fs::rename(&temp, path) {
+            let _ = std::fs::remove_file(&temp);
+            return Err(err.into());
         }
+        Ok(())
+    }
+
+    #[cfg(feature = "serde")]
+    pub fn load(path: impl AsRef<Path>) -> GameResult<Game> {
+        let json = std::fs::read_to_string(path)?;
+        let header: SaveHeader = serde_json::from_str(&json).map_err(|err| GameError::InvalidSaveFile(err.to_string()))?;
+        if header.version != SAVE_VERSION {
+            return Err(GameError::UnsupportedSaveVersion { found: header.version, supported: SAVE_VERSION });
+        }
+        let save: LoadedSave = serde_json::from_str(&json).map_err(|err| GameError::InvalidSaveFile(err.to_string()))?;
+        let game = save.game;
+        if game.num_jokers > MAX_JOKERS {
+            return Err(GameError::InvalidSaveFile(format!("{} jokers is more than a deck holds", game.num_jokers)));
+        }
+        // Seats are looked up by index everywhere, so every per-seat list
+        // must match the players
+        let seats = game.players.len();
+        if seats == 0 || game.dealer >= seats || game.current_player >= seats {
+            return Err(GameError::InvalidSaveFile(format!("seats don't match the {} players", seats)));
+        }
+        let per_seat = [game.street_bets.len(), game.to_act.len(), game.may_raise.len(), game.round_bets.len()];
+        if per_seat.iter().any(|&len| len != seats) {
+            return Err(GameError::InvalidSaveFile(format!("bets don't match the {} players", seats)));
+        }
+        Ok(game)
     }
     
     pub fn get_players(&self) -> &Vec<Player> {
@@ -59,192 +204,892 @@ impl Game {
         &self.state
     }
 
-    pub fn start_round(&mut self) {
-        println!("\n=== Starting New Round ===");
-        self.deck = Deck::new(self.num_jokers);  // Use stored number of jokers
-        self.deck.shuffle();
+    // Shuffles and plays the variant's steps up to the first decision. The
+    // rest of the round is played one decision at a time through `apply`.
+    pub fn begin_round(&mut self) -> GameResult<Vec<GameEvent>> {
+        if self.round_active {
+            return Err(GameError::RoundInProgress);
+        }
+        let seated = self.players.iter().filter(|p| p.chips > 0).count();
+        if seated < 2 {
+            return Err(GameError::InvalidAction("at least two players need chips to play a round".to_string()));
+        }
+        let needed: usize = self.variant.steps().iter()
+            .map(|step| match *step {
+                Step::Deal { count } | Step::DealFaceUp { count } => count * seated,
+                Step::Board { count } => count,
+                Step::Betting | Step::Draw => 0,
+            })
+            .sum();
+        let deck_size = self.stacked_deck.as_ref().map_or(52 + self.num_jokers as usize, Deck::len);
+        if needed > deck_size {
+            return Err(GameError::InvalidAction(format!(
+                "{} players need {} cards but the deck has {}", seated, needed, deck_size
+            )));
+        }
+
+        self.shuffle_new_deck();
         self.pot = 0;
-        self.state = GameState::Betting;
-        
-        // Deal cards to players
+        self.round_bets.fill(0);
+        self.board.clear();
         for player in &mut self.players {
-            player.hand = Some(self.deck.deal(5));
-            player.in_round = true;
+            player.in_round = player.chips > 0;
+            player.hand = player.in_round.then(|| Hand::new(Vec::new()));
+            if player.in_round {
+                player.add_hand_played();
+            }
         }
+        self.round_active = true;
+
+        let mut events = vec![GameEvent::RoundStarted { seed: self.round_seed, dealer: self.dealer }];
+        self.step = 0;
+        self.enter_step(&mut events)?;
+        self.advance(&mut events)?;
+        Ok(events)
+    }
 
-        self.betting_round();
-    }
-
-    fn betting_round(&mut self) {
-        println!("\n=== Betting Round ===");
-        let mut current_bet = self.min_bet;
-        let mut players_acted = 0;
-
-        while players_acted < self.active_players_count() {
-            let current_player = self.current_player;
-            let player_chips = self.players[current_player].chips;
-            let player_name = self.players[current_player].name.clone();
-            
-            if self.players[current_player].in_round {
-                self.show_player_status(current_player);
-                
-                println!("Current bet: {}", current_bet);
-                println!("1: Call/Check");
-                println!("2: Raise");
-                println!("3: Fold");
-                
-                let choice = self.get_player_choice(1..=3);
-                match choice {
-                    1 => {
-                        if let Ok(bet) = self.place_bet(current_player, current_bet) {
-                            self.pot += bet;
-                            println!("{} calls {}", player_name, bet);
-                        } else {
-                            self.players[current_player].in_round = false;
-                            println!("{} folds (insufficient chips)", player_name);
+    // Plays the current step and any that follow it until one needs a
+    // decision: a betting round or a draw. Past the last step comes the
+    // showdown.
+    fn enter_step(&mut self, events: &mut Vec<GameEvent>) -> GameResult<()> {
+        let steps = self.variant.steps();
+        loop {
+            let Some(&step) = steps.get(self.step) else {
+                self.state = GameState::ShowDown;
+                return Ok(());
+            };
+            match step {
+                Step::Deal { count } => {
+                    for i in 0..self.players.len() {
+                        if !self.players[i].in_round {
+                            continue;
+                        }
+                        let cards = self.deck.deal(count)?.cards;
+                        if let Some(hand) = self.players[i].hand.as_mut() {
+                            hand.add_cards(cards);
+                        }
+                    }
+                }
+                Step::DealFaceUp { count } => {
+                    for i in 0..self.players.len() {
+                        if !self.players[i].in_round {
+                            continue;
                         }
-                    },
-                    2 => {
-                        let raise = self.get_player_choice(current_bet..=player_chips);
-                        if let Ok(bet) = self.place_bet(current_player, raise) {
-                            self.pot += bet;
-                            current_bet = raise;
-                            println!("{} raises to {}", player_name, raise);
+                        let cards = self.deck.deal(count)?.cards;
+                        if let Some(hand) = self.players[i].hand.as_mut() {
+                            hand.add_face_up(cards.clone());
                         }
-                    },
-                    3 => {
-                        self.players[current_player].in_round = false;
-                        println!("{} folds", player_name);
-                    },
-                    _ => unreachable!(),
+                        events.push(GameEvent::UpCardsDealt { player: i, cards });
+                    }
+                }
+                Step::Board { count } => {
+                    let cards = self.deck.deal(count)?.cards;
+                    self.board.extend_from_slice(&cards);
+                    events.push(GameEvent::BoardDealt { cards });
+                }
+                Step::Betting => {
+                    let final_round = !steps[self.step + 1..].contains(&Step::Betting);
+                    self.state = if final_round { GameState::FinalBetting } else { GameState::Betting };
+                    self.start_betting_round();
+                    if self.is_opening_street() {
+                        self.post_forced_bets(events);
+                    }
+                    events.push(GameEvent::BettingStarted { final_round });
+                    return Ok(());
+                }
+                Step::Draw => {
+                    self.state = GameState::Drawing;
+                    self.start_drawing_round();
+                    events.push(GameEvent::DrawingStarted);
+                    return Ok(());
                 }
-                players_acted += 1;
             }
-            
-            self.next_player();
+            self.step += 1;
         }
+    }
 
-        self.state = GameState::Drawing;
-        self.drawing_round();
+    // The first betting round is the one with the forced bets
+    fn is_opening_street(&self) -> bool {
+        self.variant.steps().iter().position(|&step| step == Step::Betting) == Some(self.step)
     }
-    
 
-    fn drawing_round(&mut self) {
-        println!("\n=== Drawing Round ===");
-        for player_idx in 0..self.players.len() {
-            if !self.players[player_idx].in_round {
-                continue;
+    // Applies to rounds begun after this
+    pub fn set_variant(&mut self, variant: Box<dyn Variant>) -> GameResult<()> {
+        if self.round_active {
+            return Err(GameError::RoundInProgress);
+        }
+        self.variant = variant;
+        Ok(())
+    }
+
+    pub fn get_variant(&self) -> &dyn Variant {
+        self.variant.as_ref()
+    }
+
+    pub fn get_board(&self) -> &[Card] {
+        &self.board
+    }
+
+    // Posts the antes and then the blinds or the bring-in. A player who
+    // can't cover one goes all-in for what they have.
+    fn post_forced_bets(&mut self, events: &mut Vec<GameEvent>) {
+        let ForcedBets { ante, small_blind, big_blind, bring_in } = self.forced_bets;
+        let order = self.seats_after_button();
+        if order.len() < 2 {
+            return;
+        }
+
+        if ante > 0 {
+            for &player in &order {
+                let amount = ante.min(self.players[player].chips);
+                // Antes go in the pot but don't count towards the street bet
+                self.players[player].chips -= amount;
+                self.round_bets[player] += amount;
+                self.pot += amount;
+                events.push(self.posted(player, ForcedBet::Ante, amount));
             }
+        }
 
-            self.show_player_status(player_idx);
-            let player_name = self.players[player_idx].name.clone();
-            
-            println!("How many cards to discard? (0-3):");
-            let num_discard = self.get_player_choice(0..=3);
-            
-            if num_discard > 0 {
-                println!("{} discards {} cards", player_name, num_discard);
+        let opener = if big_blind > 0 {
+            // Heads-up the button posts the small blind
+            let (small, big) = if order.len() == 2 { (order[1], order[0]) } else { (order[0], order[1]) };
+            self.post_blind(small, ForcedBet::SmallBlind, small_blind, events);
+            self.post_blind(big, ForcedBet::BigBlind, big_blind, events);
+            self.current_bet = big_blind;
+            // The big blind still gets to act, and may raise
+            order[(order.iter().position(|&p| p == big).unwrap_or(0) + 1) % order.len()]
+        } else if bring_in > 0 {
+            let order = self.seats_from(self.first_to_act());
+            self.post_blind(order[0], ForcedBet::BringIn, bring_in, events);
+            self.current_bet = bring_in;
+            // The first raise may just complete the bring-in to the minimum bet
+            if bring_in < self.min_bet {
+                self.last_raise = self.min_bet - bring_in;
             }
+            // The bring-in acts again only if someone raises
+            self.to_act[order[0]] = false;
+            order[1]
+        } else {
+            self.first_to_act()
+        };
+
+        for (i, player) in self.players.iter().enumerate() {
+            self.to_act[i] &= player.chips > 0;
         }
-        
-        self.state = GameState::ShowDown;
-        self.showdown();
+        if self.to_act.iter().filter(|&&pending| pending).count() < 2 && self.current_bet_is_matched() {
+            self.to_act.fill(false);
+        }
+        self.current_player = opener;
+        self.advance_to_next_actor();
     }
 
-    fn showdown(&mut self) {
-        println!("\n=== Showdown ===");
-        let mut best_rank = HandRank::HighCard;
-        let mut winners = Vec::new();
+    fn post_blind(&mut self, player: usize, bet: ForcedBet, amount: u32, events: &mut Vec<GameEvent>) {
+        let amount = amount.min(self.players[player].chips);
+        if amount > 0 {
+            self.players[player].chips -= amount;
+            self.street_bets[player] += amount;
+            self.round_bets[player] += amount;
+            self.pot += amount;
+        }
+        events.push(self.posted(player, bet, amount));
+    }
 
-        // Find the winning hand(s)
-        for (i, player) in self.players.iter().enumerate() {
-            if player.in_round {
-                if let Some(hand) = &player.hand {
-                    println!("{}'s hand:", player.name);
-                    // Display hand here
-                    
-                    let rank = hand.evaluate();
-                    match rank {
-                        r if r > best_rank => {
-                            best_rank = r;
-                            winners.clear();
-                            winners.push(i);
-                        }
-                        r if r == best_rank => {
-                            winners.push(i);
-                        }
-                        _ => {}
+    fn posted(&self, player: usize, bet: ForcedBet, amount: u32) -> GameEvent {
+        GameEvent::Posted { player, bet, amount, all_in: self.players[player].chips == 0 }
+    }
+
+    // Whether every player who could still bet has put in the current bet
+    fn current_bet_is_matched(&self) -> bool {
+        self.players.iter()
+            .zip(&self.street_bets)
+            .all(|(p, &bet)| !p.in_round || p.chips == 0 || bet >= self.current_bet)
+    }
+
+    // Players in the round, in order starting with the seat after the button
+    fn seats_after_button(&self) -> Vec<usize> {
+        self.seats_from((self.dealer + 1) % self.players.len())
+    }
+
+    // Players in the round, in turn order starting at `start`
+    fn seats_from(&self, start: usize) -> Vec<usize> {
+        let seats = self.players.len();
+        (0..seats)
+            .map(|i| (start + i) % seats)
+            .filter(|&i| self.players[i].in_round)
+            .collect()
+    }
+
+    // The seat that opens the current betting round: the variant's choice,
+    // or else the seat after the button
+    fn first_to_act(&self) -> usize {
+        let steps = self.variant.steps();
+        let street = steps[..self.step.min(steps.len())].iter().filter(|&&step| step == Step::Betting).count();
+        let hands: Vec<(usize, &Hand)> = self.seats_after_button().into_iter()
+            .filter_map(|seat| self.players[seat].hand.as_ref().map(|hand| (seat, hand)))
+            .collect();
+        self.variant.first_to_act(street, &hands)
+            .unwrap_or((self.dealer + 1) % self.players.len())
+    }
+
+    // Applies to rounds begun after this
+    pub fn set_forced_bets(&mut self, forced_bets: ForcedBets) -> GameResult<()> {
+        if forced_bets.small_blind > forced_bets.big_blind {
+            return Err(GameError::InvalidBet(format!(
+                "small blind {} is bigger than the big blind {}", forced_bets.small_blind, forced_bets.big_blind
+            )));
+        }
+        self.forced_bets = forced_bets;
+        Ok(())
+    }
+
+    pub fn get_forced_bets(&self) -> ForcedBets {
+        self.forced_bets
+    }
+
+    // Moves the button; the seat after it acts first in the next round
+    pub fn set_dealer(&mut self, seat: usize) -> GameResult<()> {
+        if seat >= self.players.len() {
+            return Err(GameError::PlayerNotFound(seat.to_string()));
+        }
+        self.dealer = seat;
+        Ok(())
+    }
+
+    // Each round gets its own seed from the game seed and the number of
+    // rounds dealt so far, so a game replays exactly from its seed and a
+    // single round replays from the seed recorded for it.
+    fn shuffle_new_deck(&mut self) {
+        self.round_seed = self.seed ^ self.rounds_dealt.wrapping_mul(ROUND_SEED_MIX);
+        self.rounds_dealt += 1;
+        if let Some(mut deck) = self.stacked_deck.take() {
+            // Keeps any reshuffle of the discards tied to the game's seed
+            deck.reseed(self.round_seed);
+            self.deck = deck;
+            return;
+        }
+        self.deck = Deck::with_seed(self.num_jokers, self.round_seed);  // Use stored number of jokers
+        self.deck.shuffle();
+    }
+
+    // Restarts the sequence of round seeds; rounds dealt after this are the
+    // same for the same seed.
+    pub fn set_seed(&mut self, seed: u64) {
+        self.seed = seed;
+        self.rounds_dealt = 0;
+    }
+
+    // The next round is dealt from this deck in its given order, without
+    // shuffling. Later rounds go back to shuffled decks.
+    pub fn set_stacked_deck(&mut self, deck: Deck) {
+        self.stacked_deck = Some(deck);
+    }
+
+    // Takes effect from the next round's deck
+    pub fn set_num_jokers(&mut self, num_jokers: u32) -> GameResult<()> {
+        if num_jokers > MAX_JOKERS {
+            return Err(GameError::InvalidJokerCount(num_jokers));
+        }
+        self.num_jokers = num_jokers;
+        Ok(())
+    }
+
+    pub fn get_seed(&self) -> u64 {
+        self.seed
+    }
+
+    pub fn get_round_seed(&self) -> u64 {
+        self.round_seed
+    }
+
+    // Plays a whole round, asking each seat's controller for its decisions.
+    // There must be one controller per player, in seat order.
+    pub fn start_round(
+        &mut self,
+        controllers: &mut [Box<dyn PlayerController>],
+        events: &mut dyn EventSink,
+    ) -> GameResult<()> {
+        if controllers.len() != self.players.len() {
+            return Err(GameError::InvalidAction(format!(
+                "expected {} controllers, got {}", self.players.len(), controllers.len()
+            )));
+        }
+
+        for event in self.begin_round()? {
+            events.emit(event);
+        }
+        self.resume_round(controllers, events)
+    }
+
+    // Plays out a round that is already under way, such as one restored by
+    // `load`, asking the controllers for every remaining decision. A rejected
+    // choice is reported and asked for again, unless the error is fatal.
+    pub fn resume_round(
+        &mut self,
+        controllers: &mut [Box<dyn PlayerController>],
+        events: &mut dyn EventSink,
+    ) -> GameResult<()> {
+        if controllers.len() != self.players.len() {
+            return Err(GameError::InvalidAction(format!(
+                "expected {} controllers, got {}", self.players.len(), controllers.len()
+            )));
+        }
+
+        while self.round_active {
+            let player = self.current_player;
+            let action = {
+                let view = self.table_view(player);
+                if self.state == GameState::Drawing {
+                    Action::Draw(controllers[player].choose_discards(&view))
+                } else {
+                    controllers[player].choose_action(&view)
+                }
+            };
+
+            match self.apply(player, action) {
+                Ok(new_events) => {
+                    for event in new_events {
+                        events.emit(event);
                     }
                 }
+                // Asking again can't help when the game itself is stuck
+                Err(err) if err.is_fatal() => return Err(err),
+                Err(err) => events.emit(GameEvent::ActionRejected { player, reason: err.to_string() }),
             }
         }
+        Ok(())
+    }
+
+    // Applies one decision by the player whose turn it is and moves the
+    // round on as far as it can go without another decision.
+    pub fn apply(&mut self, player_idx: usize, action: Action) -> GameResult<Vec<GameEvent>> {
+        if !self.round_active {
+            return Err(GameError::NoRoundInProgress);
+        }
 
-        // Distribute pot to winners
-        let win_amount = self.pot / winners.len() as u32;
-        for &winner_idx in &winners {
-            let winner = &mut self.players[winner_idx];
-            winner.chips += win_amount;
-            println!("{} wins {} chips!", winner.name, win_amount);
+        let mut events = Vec::new();
+        if let Action::Draw(discards) = &action {
+            self.draw(player_idx, discards)?;
+            events.push(GameEvent::Drew { player: player_idx, count: discards.len() });
+        } else {
+            let to_call = self.get_amount_to_call(player_idx);
+            self.act(player_idx, action.clone())?;
+            events.push(self.action_event(player_idx, &action, to_call));
         }
 
-        self.state = GameState::GameOver;
+        self.advance(&mut events)?;
+        Ok(events)
     }
 
-    fn next_player(&mut self) {
-        self.current_player = (self.current_player + 1) % self.players.len();
+    // Every decision the current player may make. A raise may be to any
+    // total from the Raise listed here up to `get_max_bet`.
+    pub fn legal_actions(&self) -> Vec<Action> {
+        let player_idx = self.current_player;
+        if !self.round_active || !self.to_act.get(player_idx).copied().unwrap_or(false) {
+            return Vec::new();
+        }
+
+        match self.state {
+            GameState::Betting | GameState::FinalBetting => {
+                let mut actions = vec![Action::Fold];
+                if self.get_amount_to_call(player_idx) == 0 {
+                    actions.push(Action::Check);
+                } else {
+                    actions.push(Action::Call);
+                }
+                let max_bet = self.get_max_bet(player_idx);
+                if max_bet >= self.get_min_raise() {
+                    actions.push(Action::Raise(self.get_min_raise()));
+                }
+                // Under pot limit a big stack can't simply go all-in
+                if max_bet == self.street_bets[player_idx] + self.players[player_idx].chips {
+                    actions.push(Action::AllIn);
+                }
+                actions
+            }
+            GameState::Drawing => {
+                let Some(hand) = self.players[player_idx].hand.as_ref() else {
+                    return Vec::new();
+                };
+                let num_cards = hand.cards.len();
+                // The player's own discards can't be dealt back to them
+                let reshuffled = if self.reshuffle_discards { self.deck.discards_len() } else { 0 };
+                let available = self.deck.len() + reshuffled;
+                (0..=num_cards.min(available))
+                    .flat_map(|count| combinations(num_cards, count))
+                    .filter(|discards| self.variant.validate_draw(hand, discards).is_ok())
+                    .map(Action::Draw)
+                    .collect()
+            }
+            GameState::ShowDown | GameState::GameOver => Vec::new(),
+        }
     }
 
-    fn active_players_count(&self) -> usize {
-        self.players.iter().filter(|p| p.in_round).count()
+    // Moves through any phases that need no decision: on to the variant's
+    // next step once a betting round or a draw is finished, and to the
+    // showdown when its steps run out or only one player is left.
+    fn advance(&mut self, events: &mut Vec<GameEvent>) -> GameResult<()> {
+        loop {
+            match self.state {
+                GameState::Betting | GameState::FinalBetting if self.is_betting_complete() => {
+                    // Nothing left to play for once everyone else has folded
+                    if self.active_players_count() <= 1 {
+                        self.state = GameState::ShowDown;
+                    } else {
+                        self.step += 1;
+                        self.enter_step(events)?;
+                    }
+                }
+                GameState::Drawing if !self.to_act.iter().any(|&pending| pending) => {
+                    self.step += 1;
+                    self.enter_step(events)?;
+                }
+                GameState::ShowDown => self.showdown(events),
+                _ => return Ok(()),
+            }
+        }
+    }
+
+    fn start_drawing_round(&mut self) {
+        for (i, player) in self.players.iter().enumerate() {
+            self.to_act[i] = player.in_round;
+        }
+        self.current_player = (self.dealer + 1) % self.players.len();
+        self.advance_to_next_actor();
+    }
+
+    fn check_turn(&self, player_idx: usize) -> GameResult<()> {
+        let player = self.players.get(player_idx)
+            .ok_or_else(|| GameError::PlayerNotFound(player_idx.to_string()))?;
+        if !player.in_round {
+            return Err(GameError::PlayerNotInRound(player.name.clone()));
+        }
+        if player_idx != self.current_player || !self.to_act[player_idx] {
+            return Err(GameError::InvalidAction(format!("it is not {}'s turn", player.name)));
+        }
+        Ok(())
     }
 
-    fn show_player_status(&self, player_idx: usize) {
+    fn action_event(&self, player: usize, action: &Action, to_call: u32) -> GameEvent {
+        if self.players[player].is_all_in() {
+            return GameEvent::AllIn { player, amount: self.street_bets[player] };
+        }
+        match action {
+            Action::Fold => GameEvent::Folded { player },
+            Action::Check => GameEvent::Checked { player },
+            Action::Call => GameEvent::Called { player, amount: to_call },
+            Action::Raise(to) => GameEvent::Raised { player, to: *to },
+            Action::AllIn => GameEvent::AllIn { player, amount: self.street_bets[player] },
+            Action::Draw(discards) => GameEvent::Drew { player, count: discards.len() },
+        }
+    }
+
+    // What `player_idx` is allowed to see of the table
+    pub fn table_view(&self, player_idx: usize) -> TableView<'_> {
         let player = &self.players[player_idx];
-        println!("\n{}'s turn", player.name);
-        println!("Chips: {}", player.chips);
-        if let Some(hand) = &player.hand {
-            println!("Hand: {:?}", hand.cards);
+        TableView {
+            player: player_idx,
+            state: self.state,
+            hand: player.hand.as_ref(),
+            dealer: self.dealer,
+            board: &self.board,
+            seats: self.players.iter()
+                .zip(&self.street_bets)
+                .map(|(p, &street_bet)| SeatView {
+                    name: &p.name,
+                    chips: p.chips,
+                    in_round: p.in_round,
+                    street_bet,
+                    up_cards: p.hand.as_ref()
+                        .filter(|_| p.in_round)
+                        .map_or_else(Vec::new, |hand| hand.visible().cards),
+                })
+                .collect(),
+            pot: self.pot,
+            current_bet: self.current_bet,
+            to_call: self.get_amount_to_call(player_idx),
+            min_raise: self.get_min_raise(),
+            max_bet: self.get_max_bet(player_idx),
         }
     }
 
-    fn place_bet(&mut self, player_idx: usize, amount: u32) -> Result<u32, &'static str> {
-        if amount < self.min_bet {
-            return Err("Bet amount is below minimum");
+    // Resets the per-round bets. Without blinds or a bring-in the opening
+    // round starts at the minimum bet, which every player pays on their first
+    // action; later rounds open at zero so players may check. Action starts
+    // with the seat the variant picks, normally the one after the button.
+    pub fn start_betting_round(&mut self) {
+        let opening = self.state == GameState::Betting && self.is_opening_street();
+        self.current_bet = if opening { self.min_bet } else { 0 };
+        self.last_raise = self.min_bet.max(self.forced_bets.big_blind);
+        for (i, player) in self.players.iter().enumerate() {
+            self.street_bets[i] = 0;
+            self.to_act[i] = player.in_round && player.chips > 0;
+            self.may_raise[i] = true;
         }
-        
-        self.players[player_idx].place_bet(amount)
+        // No betting is possible once all but one player are all-in
+        if self.to_act.iter().filter(|&&pending| pending).count() < 2 {
+            self.to_act.fill(false);
+        }
+        self.current_player = self.first_to_act();
+        self.advance_to_next_actor();
     }
 
-    fn get_player_choice(&self, range: std::ops::RangeInclusive<u32>) -> u32 {
-        loop {
-            print!("Enter your choice ({}..{}): ", range.start(), range.end());
-            io::stdout().flush().unwrap();
-            
-            let mut input = String::new();
-            io::stdin().read_line(&mut input).unwrap();
-            
-            if let Ok(choice) = input.trim().parse::<u32>() {
-                if range.contains(&choice) {
-                    return choice;
+    // Applies a betting decision for the player whose turn it is.
+    pub fn act(&mut self, player_idx: usize, action: Action) -> GameResult<()> {
+        if !matches!(self.state, GameState::Betting | GameState::FinalBetting) {
+            return Err(GameError::InvalidGameState {
+                current: self.state,
+                expected: GameState::Betting,
+            });
+        }
+        self.check_turn(player_idx)?;
+        let player = &self.players[player_idx];
+
+        let to_call = self.get_amount_to_call(player_idx);
+        match action {
+            Action::Fold => self.fold(player_idx),
+            Action::Check => {
+                if to_call > 0 {
+                    return Err(GameError::InvalidAction(format!("cannot check, {} to call", to_call)));
+                }
+            }
+            Action::Call => {
+                // A player who can't cover the call puts in everything they have
+                let amount = to_call.min(player.chips);
+                self.commit_chips(player_idx, amount)?;
+            }
+            Action::Raise(amount) => {
+                let all_in = self.street_bets[player_idx] + player.chips;
+                let max = self.get_max_bet(player_idx);
+                let min = self.get_min_raise();
+                if (amount < min && amount != all_in) || (amount > max && max < all_in) {
+                    return Err(GameError::BetOutOfRange { min, max, attempted: amount });
+                }
+                if amount <= self.current_bet {
+                    return Err(GameError::InvalidAction(format!("a raise must be above the current bet of {}", self.current_bet)));
+                }
+                self.commit_chips(player_idx, amount - self.street_bets[player_idx])?;
+                self.raise_to(player_idx, amount);
+            }
+            Action::Draw(_) => {
+                return Err(GameError::InvalidAction("cards can only be drawn during the draw".to_string()));
+            }
+            Action::AllIn => {
+                let amount = self.street_bets[player_idx] + player.chips;
+                let max = self.get_max_bet(player_idx);
+                if amount > max {
+                    return Err(GameError::BetOutOfRange { min: self.get_min_raise(), max, attempted: amount });
+                }
+                self.commit_chips(player_idx, player.chips)?;
+                if amount > self.current_bet {
+                    self.raise_to(player_idx, amount);
                 }
             }
-            println!("Invalid choice, try again");
         }
+
+        self.to_act[player_idx] = false;
+        self.advance_to_next_actor();
+        Ok(())
     }
-    
+
+    // A full raise re-opens the action for everyone still able to bet and
+    // sets the size the next raise must match. An all-in short of a full
+    // raise only asks those who already acted to call the difference; they
+    // may not raise again.
+    fn raise_to(&mut self, player_idx: usize, amount: u32) {
+        let full_raise = amount >= self.get_min_raise();
+        if full_raise {
+            self.last_raise = (amount - self.current_bet).max(self.min_bet);
+        }
+        self.current_bet = amount;
+        for (i, other) in self.players.iter().enumerate() {
+            if i == player_idx || !other.in_round || other.chips == 0 {
+                self.to_act[i] = false;
+            } else if full_raise {
+                self.to_act[i] = true;
+                self.may_raise[i] = true;
+            } else if !self.to_act[i] {
+                self.to_act[i] = true;
+                self.may_raise[i] = false;
+            }
+        }
+    }
+
+    fn commit_chips(&mut self, player_idx: usize, amount: u32) -> GameResult<()> {
+        self.players[player_idx].remove_chips(amount)?;
+        self.street_bets[player_idx] += amount;
+        self.round_bets[player_idx] += amount;
+        self.pot += amount;
+        Ok(())
+    }
+
+    // The betting round is over once every player still in the hand has
+    // matched the bet or is all-in, or only one player is left.
+    pub fn is_betting_complete(&self) -> bool {
+        self.active_players_count() <= 1 || !self.to_act.iter().any(|&pending| pending)
+    }
+
+    pub fn get_current_player(&self) -> usize {
+        self.current_player
+    }
+
+    pub fn get_current_bet(&self) -> u32 {
+        self.current_bet
+    }
+
+    pub fn get_street_bet(&self, player_idx: usize) -> u32 {
+        self.street_bets.get(player_idx).copied().unwrap_or(0)
+    }
+
+    pub fn get_amount_to_call(&self, player_idx: usize) -> u32 {
+        self.current_bet.saturating_sub(self.get_street_bet(player_idx))
+    }
+
+    // Smallest total a bet may be raised to: the current bet plus the last
+    // full raise, which starts at the big blind or the minimum bet. After a
+    // bring-in it is enough to complete the bet to the minimum.
+    pub fn get_min_raise(&self) -> u32 {
+        self.current_bet + self.last_raise
+    }
+
+    // Largest total a player may bet this round: everything they have, or
+    // under pot limit the current bet plus the pot once they have called.
+    // After a short all-in the players who already acted may only call.
+    pub fn get_max_bet(&self, player_idx: usize) -> u32 {
+        let all_in = self.get_street_bet(player_idx) + self.players.get(player_idx).map_or(0, |p| p.chips);
+        if !self.may_raise.get(player_idx).copied().unwrap_or(true) {
+            return all_in.min(self.current_bet);
+        }
+        match self.variant.bet_limit() {
+            BetLimit::NoLimit => all_in,
+            BetLimit::PotLimit => {
+                let pot_after_call = self.pot + self.get_amount_to_call(player_idx);
+                all_in.min(self.current_bet + pot_after_call)
+            }
+        }
+    }
+
+    fn advance_to_next_actor(&mut self) {
+        for _ in 0..self.players.len() {
+            if self.to_act[self.current_player] {
+                return;
+            }
+            self.next_player();
+        }
+    }
+
+    // Replaces the cards at the given positions in a player's hand with
+    // fresh cards from the deck. Each player draws once, in turn.
+    pub fn draw(&mut self, player_idx: usize, discards: &[usize]) -> GameResult<()> {
+        if self.state != GameState::Drawing {
+            return Err(GameError::InvalidGameState {
+                current: self.state,
+                expected: GameState::Drawing,
+            });
+        }
+        self.check_turn(player_idx)?;
+        let player = &self.players[player_idx];
+        let hand = player.hand.as_ref()
+            .ok_or_else(|| GameError::PlayerNotInRound(player.name.clone()))?;
+
+        self.variant.validate_draw(hand, discards)?;
+        // The player's own discards go on the pile only after they're served
+        if discards.len() > self.deck.len() && self.reshuffle_discards {
+            self.deck.reshuffle_discards();
+        }
+
+        let replacements = self.deck.deal(discards.len())?;
+        if let Some(hand) = self.players[player_idx].hand.as_mut() {
+            let discarded = hand.discard(discards);
+            hand.add_cards(replacements.cards);
+            self.deck.discard(discarded);
+        }
+        self.to_act[player_idx] = false;
+        self.advance_to_next_actor();
+        Ok(())
+    }
+
+    // Folded hands are mucked onto the discard pile
+    fn fold(&mut self, player_idx: usize) {
+        let player = &mut self.players[player_idx];
+        player.in_round = false;
+        if let Some(hand) = player.hand.take() {
+            self.deck.discard(hand.cards);
+        }
+    }
+
+    // House rule: when the stub can't cover a draw, the discard pile is
+    // shuffled in. Off by default, in which case the draw fails instead.
+    pub fn set_reshuffle_discards(&mut self, reshuffle: bool) {
+        self.reshuffle_discards = reshuffle;
+    }
+
+    // The draw limit of the joker draw game, see JokerDraw
+    pub fn max_discards(hand: &Hand, discards: &[usize]) -> usize {
+        JokerDraw::max_discards(hand, discards)
+    }
+
+    fn showdown(&mut self, events: &mut Vec<GameEvent>) {
+        events.push(GameEvent::ShowdownStarted);
+        // An uncontested winner keeps their cards hidden
+        let contested = self.active_players_count() > 1;
+        for (player, p) in self.players.iter().enumerate().filter(|(_, p)| contested && p.in_round) {
+            if let Some(hand) = &p.hand {
+                events.push(GameEvent::HandShown {
+                    player,
+                    cards: hand.cards.clone(),
+                    evaluation: self.variant.evaluate(hand, &self.board),
+                });
+            }
+        }
+
+        for (pot, winners) in self.award_pots().into_iter().enumerate() {
+            for (player, amount) in winners {
+                events.push(GameEvent::PotAwarded { pot, player, amount });
+            }
+        }
+
+        self.state = GameState::GameOver;
+        self.round_active = false;
+        self.move_button();
+        events.push(GameEvent::RoundOver);
+    }
+
+    // Passes the button to the next seat that still has chips
+    fn move_button(&mut self) {
+        let seats = self.players.len();
+        if let Some(next) = (1..=seats).map(|i| (self.dealer + i) % seats).find(|&i| self.players[i].chips > 0) {
+            self.dealer = next;
+        }
+    }
+
+    pub fn get_dealer(&self) -> usize {
+        self.dealer
+    }
+
+    // Splits the pot by how much each player put in over the hand. Each pot
+    // is capped at an all-in player's total, and only players who matched
+    // that total and are still in the hand may win it.
+    pub fn side_pots(&self) -> Vec<Pot> {
+        let mut levels: Vec<u32> = self.round_bets.iter().copied().filter(|&bet| bet > 0).collect();
+        levels.sort_unstable();
+        levels.dedup();
+
+        let mut pots: Vec<Pot> = Vec::new();
+        let mut previous = 0;
+        for level in levels {
+            let amount = self.round_bets.iter()
+                .map(|&bet| bet.min(level) - bet.min(previous))
+                .sum();
+            let eligible: Vec<usize> = (0..self.players.len())
+                .filter(|&i| self.players[i].in_round && self.round_bets[i] >= level)
+                .collect();
+            previous = level;
+
+            // Chips nobody still in the hand matched go back to the last pot
+            // they could be won from, as do levels with the same contenders.
+            match pots.last_mut() {
+                Some(last) if eligible.is_empty() || last.eligible == eligible => last.amount += amount,
+                _ => pots.push(Pot { amount, eligible }),
+            }
+        }
+
+        // Chips that weren't bet through this hand, such as a pot set up
+        // directly, belong to the main pot.
+        let bet_total: u32 = self.round_bets.iter().sum();
+        let extra = self.pot.saturating_sub(bet_total);
+        if extra > 0 {
+            match pots.first_mut() {
+                Some(main) => main.amount += extra,
+                None => pots.push(Pot {
+                    amount: extra,
+                    eligible: (0..self.players.len()).filter(|&i| self.players[i].in_round).collect(),
+                }),
+            }
+        }
+        pots
+    }
+
+    // Awards every pot to the best hand among its eligible players and empties
+    // the pot. Returns the (player, chips) payouts for each pot in the order
+    // of side_pots. Odd chips from a split go to the winners nearest the
+    // button on its left.
+    pub fn award_pots(&mut self) -> Vec<Vec<(usize, u32)>> {
+        let values: Vec<Option<HandValue>> = self.players.iter()
+            .map(|p| p.hand.as_ref().filter(|_| p.in_round).map(|hand| self.variant.value(hand, &self.board)))
+            .collect();
+
+        let seats = self.players.len();
+        let mut payouts = Vec::new();
+        let mut winners_overall = Vec::new();
+        for pot in self.side_pots() {
+            let best = pot.eligible.iter().filter_map(|&i| values[i].as_ref()).max();
+            let winners: Vec<usize> = pot.eligible.iter()
+                .copied()
+                .filter(|&i| best.is_some() && values[i].as_ref() == best)
+                .collect();
+
+            let mut pot_payouts = Vec::new();
+            if !winners.is_empty() {
+                let share = pot.amount / winners.len() as u32;
+                let odd_chips = pot.amount % winners.len() as u32;
+                // Odd chips go one each to the first winners left of the button
+                let mut by_position = winners.clone();
+                by_position.sort_by_key(|&i| (i + seats - self.dealer - 1) % seats);
+                let odd_winners = &by_position[..odd_chips as usize];
+                for &winner_idx in &winners {
+                    let amount = share + u32::from(odd_winners.contains(&winner_idx));
+                    self.players[winner_idx].chips += amount;
+                    pot_payouts.push((winner_idx, amount));
+                    if !winners_overall.contains(&winner_idx) {
+                        winners_overall.push(winner_idx);
+                    }
+                }
+            }
+            payouts.push(pot_payouts);
+        }
+
+        for winner_idx in winners_overall {
+            self.players[winner_idx].add_win();
+        }
+        self.pot = 0;
+        self.round_bets.fill(0);
+        payouts
+    }
+
+    pub fn get_round_bet(&self, player_idx: usize) -> u32 {
+        self.round_bets.get(player_idx).copied().unwrap_or(0)
+    }
+
+    fn next_player(&mut self) {
+        self.current_player = (self.current_player + 1) % self.players.len();
+    }
+
+    fn active_players_count(&self) -> usize {
+        self.players.iter().filter(|p| p.in_round).count()
+    }
+
+    pub fn get_deck(&self) -> &Deck {
+        &self.deck
+    }
+
     pub fn set_player_hand(&mut self, player_idx: usize, hand: Hand) {
         if player_idx < self.players.len() {
             self.players[player_idx].hand = Some(hand);
         }
     }
     
-    pub fn test_place_bet(&mut self, player_idx: usize, amount: u32) -> Result<u32, &'static str> {
-        if amount < self.min_bet {
-            return Err("Bet amount is below minimum");
+    pub fn test_place_bet(&mut self, player_idx: usize, amount: u32) -> GameResult<u32> {
+        let player = self.players.get(player_idx)
+            .ok_or_else(|| GameError::PlayerNotFound(player_idx.to_string()))?;
+        if !player.in_round {
+            return Err(GameError::PlayerNotInRound(player.name.clone()));
         }
-        
-        if player_idx >= self.players.len() {
-            return Err("Invalid player index");
+        if amount < self.min_bet {
+            return Err(GameError::BetOutOfRange {
+                min: self.min_bet,
+                max: player.chips,
+                attempted: amount,
+            });
         }
         
         let result = self.players[player_idx].place_bet(amount);
@@ -252,6 +1097,8 @@ impl Game {
         // If successful, add to pot
         if result.is_ok() {
             self.pot += amount;
+            self.street_bets[player_idx] += amount;
+            self.round_bets[player_idx] += amount;
         }
         
         result
@@ -259,7 +1106,7 @@ impl Game {
     
     pub fn test_fold_player(&mut self, player_idx: usize) {
         if player_idx < self.players.len() {
-            self.players[player_idx].in_round = false;
+            self.fold(player_idx);
         }
     }
 
@@ -267,22 +1114,44 @@ impl Game {
         matches!(self.state, GameState::GameOver)
     }
 
+    pub fn is_round_active(&self) -> bool {
+        self.round_active
+    }
+
+    // Also moves to the matching step of the variant, so the round carries
+    // on from there. Entering the draw lets every player draw again.
     pub fn set_game_state(&mut self, state: GameState) {
+        let steps = self.variant.steps();
+        let step = match state {
+            GameState::Betting => steps.iter().position(|s| *s == Step::Betting),
+            GameState::Drawing => steps.iter().position(|s| *s == Step::Draw),
+            GameState::FinalBetting => steps.iter().rposition(|s| *s == Step::Betting),
+            _ => None,
+        };
+        if let Some(step) = step {
+            self.step = step;
+        }
         self.state = state;
+        if state == GameState::Drawing {
+            self.start_drawing_round();
+        }
     }
     
     pub fn setup_test_round(&mut self) {
-        self.deck = Deck::new(self.num_jokers);
-        self.deck.shuffle();
+        self.shuffle_new_deck();
         self.pot = 0;
-        self.state = GameState::Betting;
+        self.round_bets.fill(0);
+        self.set_game_state(GameState::Betting);
         self.current_player = 0;
+        self.round_active = true;
+        self.board.clear();
         
         for player in &mut self.players {
-            player.hand = Some(self.deck.deal(5));
+            player.hand = Some(self.deck.deal(5).expect("a fresh deck covers every player"));
             player.in_round = true;
             player.add_hand_played();
         }
+        self.start_betting_round();
     }
     
     pub fn distribute_pot_to_player(&mut self, player_idx: usize) {
@@ -302,6 +1171,7 @@ impl Game {
         
         self.distribute_pot_to_player(winner_idx);
         self.state = GameState::GameOver;
+        self.round_active = false;
     }
 
     pub fn get_players_mut(&mut self) -> &mut Vec<Player> {
@@ -1,8 +1,12 @@
 use std::collections::HashMap;
-use crate::card::{Card, Rank, Suit};
+use crate::card::{parse_card, Card, JokerId, Rank, Suit};
+use crate::card_set::MAX_JOKERS;
+use crate::error::GameError;
 use std::fmt;
+use std::str::FromStr;
 
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum HandRank {
     HighCard,
     OnePair,
@@ -17,37 +21,222 @@ pub enum HandRank {
     FiveOfAKind,  // Only possible with jokers
 }
 
+// A fully comparable hand strength: the category first, then the ranks
+// that break ties within it, most significant first (e.g. the pair rank
+// followed by the kickers for OnePair).
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HandValue {
+    pub category: HandRank,
+    pub tiebreak: Vec<Rank>,
+}
+
+// The card a joker was resolved to; `index` is its position in `Hand::cards`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct JokerAssignment {
+    pub index: usize,
+    pub rank: Rank,
+    pub suit: Suit,
+}
+
+impl fmt::Display for JokerAssignment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Joker as {}{}", self.rank.symbol(), self.suit.symbol())
+    }
+}
+
+// The full result of evaluating a hand: its value, the cards that make it up
+// and what each joker among them stands for.
+#[derive(Debug, Clone)]
+pub struct HandEvaluation {
+    pub value: HandValue,
+    pub cards: Vec<Card>,
+    pub jokers: Vec<JokerAssignment>,
+}
+
+impl HandEvaluation {
+    // The winning cards with every joker replaced by its assignment.
+    pub fn resolved_cards(&self) -> Vec<Card> {
+        let mut assignments = self.jokers.iter();
+        self.cards.iter()
+            .map(|card| match card.is_joker().then(|| assignments.next()).flatten() {
+                Some(joker) => Card::new(joker.rank, joker.suit),
+                None => *card,
+            })
+            .collect()
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Hand {
     pub cards: Vec<Card>,
+    // Which cards everyone can see, by position. Cards past the end of this
+    // are face down, so hands that are never shown leave it empty.
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Vec::is_empty"))]
+    face_up: Vec<bool>,
 }
 
 impl Hand {
     pub fn new(cards: Vec<Card>) -> Self {
-        Hand { cards }
+        Hand { cards, face_up: Vec::new() }
+    }
+
+    pub fn is_face_up(&self, index: usize) -> bool {
+        self.face_up.get(index).copied().unwrap_or(false)
+    }
+
+    pub fn set_face_up(&mut self, index: usize, face_up: bool) {
+        if index < self.cards.len() {
+            self.face_up.resize(self.face_up.len().max(index + 1), false);
+            self.face_up[index] = face_up;
+        }
+    }
+
+    // Adds cards that everyone can see, e.g. the up-cards of stud
+    pub fn add_face_up(&mut self, cards: Vec<Card>) {
+        self.face_up.resize(self.cards.len(), false);
+        self.cards.extend(cards);
+        self.face_up.resize(self.cards.len(), true);
+    }
+
+    // The face-up cards on their own, in the order they were dealt
+    pub fn visible(&self) -> Hand {
+        Hand::new(self.cards.iter()
+            .enumerate()
+            .filter(|&(i, _)| self.is_face_up(i))
+            .map(|(_, &card)| card)
+            .collect())
+    }
+
+    // What the other players can make of this hand. With fewer than five
+    // cards showing only pairs, trips and quads count, never a straight or
+    // a flush.
+    pub fn visible_value(&self) -> HandValue {
+        self.visible().value()
+    }
+
+    // Removes the cards at the given positions and returns them.
+    pub fn discard(&mut self, indices: &[usize]) -> Vec<Card> {
+        let mut sorted: Vec<usize> = indices.iter()
+            .copied()
+            .filter(|&i| i < self.cards.len())
+            .collect();
+        // Remove from the back so earlier positions stay valid
+        sorted.sort_unstable_by(|a, b| b.cmp(a));
+        sorted.dedup();
+        let mut discarded: Vec<Card> = sorted.into_iter()
+            .map(|i| {
+                if i < self.face_up.len() {
+                    self.face_up.remove(i);
+                }
+                self.cards.remove(i)
+            })
+            .collect();
+        discarded.reverse();
+        discarded
+    }
+
+    pub fn add_cards(&mut self, cards: Vec<Card>) {
+        self.cards.extend(cards);
+    }
+
+    // Like `evaluate`, but also ranks hands within the same category.
+    // Jokers are resolved to whichever cards make the strongest hand; a joker
+    // may stand for any card, including one already held (e.g. five of a
+    // kind, or a double-ace flush).
+    pub fn value(&self) -> HandValue {
+        #[cfg(feature = "lookup")]
+        return crate::lookup::evaluate(&self.cards);
+
+        #[cfg(not(feature = "lookup"))]
+        self.evaluation().value
+    }
+
+    // Evaluates the hand and reports which card each joker was resolved to.
+    // Hands of more than five cards are scored by their best five.
+    pub fn evaluation(&self) -> HandEvaluation {
+        if self.cards.len() > 5 {
+            return self.best_of(&combinations(self.cards.len(), 5));
+        }
+        let indices: Vec<usize> = (0..self.cards.len()).collect();
+        self.evaluate_subset(&indices)
+    }
+
+    // Finds the best five-card hand among 5 to 9 cards, e.g. hole cards plus
+    // a board. Returns None for any other number of cards.
+    pub fn best_five(&self) -> Option<HandEvaluation> {
+        if !(5..=9).contains(&self.cards.len()) {
+            return None;
+        }
+        Some(self.best_of(&combinations(self.cards.len(), 5)))
+    }
+
+    // Finds the best five-card hand that takes exactly `from_hole` of the
+    // first `hole` cards and the rest from the cards after them, e.g. the
+    // two hole cards and three board cards of Omaha. Returns None when there
+    // are too few cards on either side.
+    pub fn best_five_using(&self, hole: usize, from_hole: usize) -> Option<HandEvaluation> {
+        let board = self.cards.len().checked_sub(hole)?;
+        let from_board = 5usize.checked_sub(from_hole)?;
+        if hole < from_hole || board < from_board {
+            return None;
+        }
+        let subsets: Vec<Vec<usize>> = combinations(hole, from_hole).into_iter()
+            .flat_map(|hole_cards| {
+                combinations(board, from_board).into_iter().map(move |board_cards| {
+                    hole_cards.iter().copied().chain(board_cards.iter().map(|&i| hole + i)).collect()
+                })
+            })
+            .collect();
+        Some(self.best_of(&subsets))
+    }
+
+    fn best_of(&self, subsets: &[Vec<usize>]) -> HandEvaluation {
+        subsets.iter()
+            .map(|indices| self.evaluate_subset(indices))
+            .reduce(|best, candidate| if candidate.value > best.value { candidate } else { best })
+            .unwrap_or_else(|| self.evaluate_subset(&[]))
+    }
+
+    fn evaluate_subset(&self, indices: &[usize]) -> HandEvaluation {
+        let cards: Vec<Card> = indices.iter().map(|&i| self.cards[i]).collect();
+        let (mut naturals, num_jokers) = split_jokers(&cards);
+        let substitutes = best_substitutes(&naturals, num_jokers);
+
+        let jokers = indices.iter()
+            .filter(|&&i| self.cards[i].is_joker())
+            .zip(&substitutes)
+            .map(|(&index, &(rank, suit))| JokerAssignment { index, rank, suit })
+            .collect();
+
+        naturals.extend(substitutes);
+        HandEvaluation {
+            value: concrete_value(&naturals),
+            cards,
+            jokers,
+        }
     }
 
     pub fn evaluate(&self) -> HandRank {
-        let num_jokers = self.cards.iter().filter(|c| c.is_joker).count();
+        let (non_joker_cards, num_jokers) = split_jokers(&self.cards);
 
         // Special case: all jokers
         if num_jokers == 5 {
             return HandRank::FiveOfAKind;
         }
 
-        let non_joker_cards: Vec<&Card> = self.cards.iter().filter(|c| !c.is_joker).collect();
-
         // Count ranks and suits
         let mut rank_counts: HashMap<Rank, usize> = HashMap::new();
         let mut suit_counts: HashMap<Suit, usize> = HashMap::new();
 
-        for card in &non_joker_cards {
-            *rank_counts.entry(card.rank).or_insert(0) += 1;
-            *suit_counts.entry(card.suit).or_insert(0) += 1;
+        for &(rank, suit) in &non_joker_cards {
+            *rank_counts.entry(rank).or_insert(0) += 1;
+            *suit_counts.entry(suit).or_insert(0) += 1;
         }
 
         // Sort rank counts in descending order
         let mut rank_count_vec: Vec<(Rank, usize)> = rank_counts.into_iter().collect();
-        rank_count_vec.sort_by(|a, b| b.1.cmp(&a.1));
+        rank_count_vec.sort_by_key(|&(_, count)| std::cmp::Reverse(count));
 
         // Check for Five of a Kind (requires jokers)
         if !rank_count_vec.is_empty() {
@@ -111,14 +300,14 @@ impl Hand {
         HandRank::HighCard
     }
 
-    fn is_royal_flush(&self, num_jokers: usize, suit_counts: &HashMap<Suit, usize>, non_joker_cards: &[&Card]) -> bool {
+    fn is_royal_flush(&self, num_jokers: usize, suit_counts: &HashMap<Suit, usize>, non_joker_cards: &[(Rank, Suit)]) -> bool {
         if let Some((&suit, &count)) = suit_counts.iter().max_by_key(|&(_, count)| count) {
             if count + num_jokers >= 5 {
-                let royal_ranks = vec![Rank::Ten, Rank::Jack, Rank::Queen, Rank::King, Rank::Ace];
+                let royal_ranks = [Rank::Ten, Rank::Jack, Rank::Queen, Rank::King, Rank::Ace];
                 let mut missing_ranks = royal_ranks.len();
 
-                for card in non_joker_cards {
-                    if card.suit == suit && royal_ranks.contains(&card.rank) {
+                for (rank, card_suit) in non_joker_cards {
+                    if *card_suit == suit && royal_ranks.contains(rank) {
                         missing_ranks -= 1;
                     }
                 }
@@ -129,11 +318,11 @@ impl Hand {
         false
     }
 
-    fn is_straight_flush(&self, num_jokers: usize, suit_counts: &HashMap<Suit, usize>, non_joker_cards: &[&Card]) -> bool {
+    fn is_straight_flush(&self, num_jokers: usize, suit_counts: &HashMap<Suit, usize>, non_joker_cards: &[(Rank, Suit)]) -> bool {
         if let Some((&suit, &count)) = suit_counts.iter().max_by_key(|&(_, count)| count) {
             if count + num_jokers >= 5 {
-                let suited_cards: Vec<&Card> = non_joker_cards.iter()
-                    .filter(|c| c.suit == suit)
+                let suited_cards: Vec<(Rank, Suit)> = non_joker_cards.iter()
+                    .filter(|&&(_, card_suit)| card_suit == suit)
                     .copied()
                     .collect();
                 return self.can_form_straight(&suited_cards, num_jokers);
@@ -163,30 +352,26 @@ impl Hand {
         suit_counts.values().any(|&count| count + num_jokers >= 5)
     }
 
-    fn is_straight(&self, num_jokers: usize, non_joker_cards: &[&Card]) -> bool {
+    fn is_straight(&self, num_jokers: usize, non_joker_cards: &[(Rank, Suit)]) -> bool {
         self.can_form_straight(non_joker_cards, num_jokers)
     }
 
-    fn can_form_straight(&self, cards: &[&Card], num_jokers: usize) -> bool {
+    fn can_form_straight(&self, cards: &[(Rank, Suit)], num_jokers: usize) -> bool {
         if cards.is_empty() && num_jokers < 5 {
             return false;
         }
 
-        let mut ranks: Vec<Rank> = cards.iter().map(|c| c.rank).collect();
+        let mut ranks: Vec<Rank> = cards.iter().map(|&(rank, _)| rank).collect();
         ranks.sort();
         ranks.dedup();
 
-        // Check each possible starting rank
-        for &rank in &ranks {
-            let rank_val = rank as u8;
-            let mut missing_ranks = 0;
-
-            for i in 0..5 {
-                let needed_rank = ((rank_val + i) % 13) as u8;
-                if !ranks.contains(&Rank::from_u8(needed_rank).unwrap_or(Rank::Two)) {
-                    missing_ranks += 1;
-                }
-            }
+        // Check every window from 2-6 up to T-A. Straights don't wrap
+        // around past the Ace (no K-A-2), the wheel is handled below.
+        for start in 0..=Rank::Ten.to_u8() {
+            let missing_ranks = (start..start + 5)
+                .filter_map(Rank::from_u8)
+                .filter(|rank| !ranks.contains(rank))
+                .count();
 
             if missing_ranks <= num_jokers {
                 return true;
@@ -195,7 +380,7 @@ impl Hand {
 
         // Special case: Ace-low straight (A,2,3,4,5)
         if ranks.contains(&Rank::Ace) {
-            let low_straight = vec![Rank::Ace, Rank::Two, Rank::Three, Rank::Four, Rank::Five];
+            let low_straight = [Rank::Ace, Rank::Two, Rank::Three, Rank::Four, Rank::Five];
             let mut missing_ranks = 0;
 
             for rank in low_straight {
@@ -225,10 +410,206 @@ impl Hand {
     }
 }
 
+// Every way of choosing `k` of the indices `0..n`, in lexicographic order.
+pub(crate) fn combinations(n: usize, k: usize) -> Vec<Vec<usize>> {
+    if k > n {
+        return Vec::new();
+    }
+    let mut result = Vec::new();
+    let mut indices: Vec<usize> = (0..k).collect();
+    loop {
+        result.push(indices.clone());
+        // Advance the rightmost index that still has room to move
+        let Some(i) = (0..k).rev().find(|&i| indices[i] < n - k + i) else {
+            return result;
+        };
+        indices[i] += 1;
+        for j in i + 1..k {
+            indices[j] = indices[j - 1] + 1;
+        }
+    }
+}
+
+fn split_jokers(cards: &[Card]) -> (Vec<(Rank, Suit)>, usize) {
+    let naturals: Vec<(Rank, Suit)> = cards.iter().filter_map(Card::natural).collect();
+    let num_jokers = cards.len() - naturals.len();
+    (naturals, num_jokers)
+}
+
+fn rank_counts(cards: &[(Rank, Suit)]) -> [usize; 13] {
+    let mut counts = [0; 13];
+    for &(rank, _) in cards {
+        counts[rank.to_u8() as usize] += 1;
+    }
+    counts
+}
+
+// The five ranks of the straight topped by `top` (Five for the wheel).
+fn straight_window(top: Rank) -> [Rank; 5] {
+    let top = top.to_u8();
+    let mut window = [Rank::Ace; 5];
+    for (i, slot) in window.iter_mut().enumerate() {
+        // The wheel wraps around to the Ace, nothing else does
+        *slot = Rank::from_u8((top + 13 - i as u8) % 13).unwrap_or(Rank::Ace);
+    }
+    window
+}
+
+// Ranks that can top a straight, best first.
+fn straight_tops() -> impl Iterator<Item = Rank> {
+    Rank::ALL.into_iter().rev().take_while(|&rank| rank >= Rank::Five)
+}
+
+// Adds a joker playing `rank` until the hand holds `size` of that rank.
+// Without an explicit suit it avoids cards already held where possible.
+fn push_substitutes(resolved: &mut Vec<(Rank, Suit)>, rank: Rank, suit: Option<Suit>, size: usize) {
+    while resolved.iter().filter(|&&(r, _)| r == rank).count() < size {
+        let suit = suit.unwrap_or_else(|| {
+            Suit::ALL.into_iter()
+                .find(|&s| !resolved.contains(&(rank, s)))
+                .unwrap_or(Suit::Spades)
+        });
+        resolved.push((rank, suit));
+    }
+}
+
+// Chooses the cards that the jokers should stand for so that the resulting
+// hand scores as high as possible.
+fn best_substitutes(naturals: &[(Rank, Suit)], num_jokers: usize) -> Vec<(Rank, Suit)> {
+    if num_jokers == 0 {
+        return Vec::new();
+    }
+
+    let total = naturals.len() + num_jokers;
+    let counts = rank_counts(naturals);
+    let count = |rank: Rank| counts[rank.to_u8() as usize];
+    let mut resolved = naturals.to_vec();
+
+    let single_suit = naturals.first()
+        .map(|&(_, suit)| suit)
+        .filter(|&suit| naturals.iter().all(|&(_, s)| s == suit));
+    let best_straight = || -> Option<[Rank; 5]> {
+        if total != 5 || counts.iter().any(|&c| c > 1) {
+            return None;
+        }
+        straight_tops()
+            .map(straight_window)
+            .find(|window| naturals.iter().all(|(rank, _)| window.contains(rank)))
+    };
+    // Highest rank that the jokers can bring up to `size` of a kind
+    let best_of_a_kind = |size: usize| -> Option<Rank> {
+        if total < size {
+            return None;
+        }
+        Rank::ALL.into_iter().rev().find(|&rank| count(rank) + num_jokers >= size)
+    };
+
+    if let Some(rank) = best_of_a_kind(5) {
+        push_substitutes(&mut resolved, rank, None, 5);
+    } else if let (Some(suit), Some(window)) = (single_suit, best_straight()) {
+        for rank in window {
+            push_substitutes(&mut resolved, rank, Some(suit), 1);
+        }
+    } else if let Some(rank) = best_of_a_kind(4) {
+        push_substitutes(&mut resolved, rank, None, 4);
+    } else if let Some((trips, pair)) = best_full_house(&counts, total, num_jokers) {
+        push_substitutes(&mut resolved, trips, None, 3);
+        push_substitutes(&mut resolved, pair, None, 2);
+    } else if let (Some(suit), 5) = (single_suit, total) {
+        // Every joker plays as an ace of the flush suit
+        while resolved.len() < total {
+            resolved.push((Rank::Ace, suit));
+        }
+    } else if let Some(window) = best_straight() {
+        // Keep the jokers off-suit so they never complete a flush by accident
+        let suit = Suit::ALL.into_iter()
+            .find(|&s| naturals.first().is_none_or(|&(_, suit)| suit != s))
+            .unwrap_or(Suit::Spades);
+        for rank in window {
+            push_substitutes(&mut resolved, rank, Some(suit), 1);
+        }
+    } else if let Some(rank) = best_of_a_kind(3) {
+        push_substitutes(&mut resolved, rank, None, 3);
+    } else if let Some(rank) = best_of_a_kind(2) {
+        // A joker always turns two pair into a full house, so with jokers
+        // in hand a pair is the only thing left to make
+        push_substitutes(&mut resolved, rank, None, 2);
+    } else {
+        // A lone joker is simply an ace
+        push_substitutes(&mut resolved, Rank::Ace, None, 1);
+    }
+
+    resolved.split_off(naturals.len())
+}
+
+fn best_full_house(counts: &[usize; 13], total: usize, num_jokers: usize) -> Option<(Rank, Rank)> {
+    if total != 5 {
+        return None;
+    }
+    let count = |rank: Rank| counts[rank.to_u8() as usize];
+    for trips in Rank::ALL.into_iter().rev() {
+        for pair in Rank::ALL.into_iter().rev().filter(|&r| r != trips) {
+            let others = Rank::ALL.iter()
+                .filter(|&&r| r != trips && r != pair)
+                .map(|&r| count(r))
+                .sum::<usize>();
+            if others == 0 && count(trips) <= 3 && count(pair) <= 2
+                && (3 - count(trips)) + (2 - count(pair)) <= num_jokers
+            {
+                return Some((trips, pair));
+            }
+        }
+    }
+    None
+}
+
+// Scores a hand of at most five cards that contains no jokers.
+fn concrete_value(cards: &[(Rank, Suit)]) -> HandValue {
+    let counts = rank_counts(cards);
+
+    // Rank groups ordered by size, then by rank, largest first
+    let mut groups: Vec<(usize, Rank)> = Rank::ALL.into_iter()
+        .rev()
+        .filter(|&rank| counts[rank.to_u8() as usize] > 0)
+        .map(|rank| (counts[rank.to_u8() as usize], rank))
+        .collect();
+    groups.sort_by_key(|&(size, _)| std::cmp::Reverse(size));
+    let group_ranks: Vec<Rank> = groups.iter().map(|&(_, rank)| rank).collect();
+    let mut descending: Vec<Rank> = cards.iter().map(|&(rank, _)| rank).collect();
+    descending.sort_by(|a, b| b.cmp(a));
+
+    let is_flush = cards.len() == 5 && cards.iter().all(|&(_, suit)| suit == cards[0].1);
+    let straight_top = if cards.len() == 5 && groups.len() == 5 {
+        straight_tops().find(|&top| {
+            straight_window(top).iter().all(|rank| counts[rank.to_u8() as usize] == 1)
+        })
+    } else {
+        None
+    };
+    let sizes: Vec<usize> = groups.iter().map(|&(size, _)| size).collect();
+
+    let (category, tiebreak) = match (sizes.as_slice(), straight_top, is_flush) {
+        ([5, ..], _, _) => (HandRank::FiveOfAKind, group_ranks),
+        (_, Some(Rank::Ace), true) => (HandRank::RoyalFlush, vec![Rank::Ace]),
+        (_, Some(top), true) => (HandRank::StraightFlush, vec![top]),
+        ([4, ..], _, _) => (HandRank::FourOfAKind, group_ranks),
+        ([3, 2], _, _) => (HandRank::FullHouse, group_ranks),
+        (_, _, true) => (HandRank::Flush, descending),
+        (_, Some(top), _) => (HandRank::Straight, vec![top]),
+        ([3, ..], _, _) => (HandRank::ThreeOfAKind, group_ranks),
+        ([2, 2, ..], _, _) => (HandRank::TwoPair, group_ranks),
+        ([2, ..], _, _) => (HandRank::OnePair, group_ranks),
+        _ => (HandRank::HighCard, descending),
+    };
+
+    HandValue { category, tiebreak }
+}
+
 impl Clone for Hand {
     fn clone(&self) -> Self {
         Hand {
             cards: self.cards.clone(),
+            face_up: self.face_up.clone(),
         }
     }
 }
@@ -239,6 +620,69 @@ impl fmt::Debug for Hand {
     }
 }
 
+// Cards in notation separated by spaces, e.g. "Ah Kd Tc 9s Jk"; `{:#}` uses
+// suit symbols.
+impl fmt::Display for Hand {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, card) in self.cards.iter().enumerate() {
+            if i > 0 {
+                write!(f, " ")?;
+            }
+            if f.alternate() {
+                write!(f, "{:#}", card)?;
+            } else {
+                write!(f, "{}", card)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+// Cards may be separated by spaces or commas. Each plain "Jk" is a different
+// joker, numbered from 0 in the order they appear; "Jk2" keeps its number.
+impl FromStr for Hand {
+    type Err = GameError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // A bare "Jk" takes the lowest joker id not given explicitly
+        let parsed = s.split(|c: char| c.is_whitespace() || c == ',')
+            .filter(|part| !part.is_empty())
+            .enumerate()
+            .map(|(i, part)| {
+                if part.eq_ignore_ascii_case("jk") {
+                    return Ok(None);
+                }
+                parse_card(part)
+                    .map(Some)
+                    .map_err(|reason| GameError::InvalidCardNotation(format!("card {}: {}", i + 1, reason)))
+            })
+            .collect::<Result<Vec<Option<Card>>, GameError>>()?;
+
+        let mut used: Vec<u8> = parsed.iter()
+            .filter_map(|card| match card {
+                Some(Card::Joker(JokerId(id))) => Some(*id),
+                _ => None,
+            })
+            .collect();
+        let jokers = parsed.iter().filter(|card| card.is_none_or(|card| card.is_joker())).count() as u32;
+        let mut cards = Vec::with_capacity(parsed.len());
+        for card in parsed {
+            let card = match card {
+                Some(card) => card,
+                None => {
+                    let id = (0..MAX_JOKERS as u8)
+                        .find(|id| !used.contains(id))
+                        .ok_or(GameError::InvalidJokerCount(jokers))?;
+                    used.push(id);
+                    Card::joker(id)
+                }
+            };
+            cards.push(card);
+        }
+        Ok(Hand::new(cards))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -246,13 +690,13 @@ mod tests {
     // Helper function for tests
     fn create_test_cards(ranks: Vec<Rank>, suits: Vec<Suit>, jokers: usize) -> Vec<Card> {
         let mut cards: Vec<Card> = ranks.into_iter()
-            .zip(suits.into_iter())
-            .map(|(rank, suit)| Card::new(rank, suit, false))
+            .zip(suits)
+            .map(|(rank, suit)| Card::new(rank, suit))
             .collect();
         
         // Add jokers
-        for _ in 0..jokers {
-            cards.push(Card::new(Rank::Ace, Suit::Hearts, true));
+        for id in 0..jokers {
+            cards.push(Card::joker(id as u8));
         }
         
         cards
@@ -324,4 +768,54 @@ mod tests {
             assert_eq!(hand.evaluate(), HandRank::FourOfAKind);
         }
     }
-}
\ No newline at end of file
+
+    mod value_tests {
+        use super::*;
+
+        #[test]
+        fn test_pair_ranks_above_kickers() {
+            let aces = Hand::new(create_test_cards(
+                vec![Rank::Ace, Rank::Ace, Rank::Four, Rank::Three, Rank::Two],
+                vec![Suit::Hearts, Suit::Diamonds, Suit::Clubs, Suit::Spades, Suit::Hearts],
+                0
+            ));
+            let value = aces.value();
+            assert_eq!(value.category, HandRank::OnePair);
+            assert_eq!(value.tiebreak, vec![Rank::Ace, Rank::Four, Rank::Three, Rank::Two]);
+        }
+
+        #[test]
+        fn test_joker_completes_highest_straight() {
+            let hand = Hand::new(create_test_cards(
+                vec![Rank::Nine, Rank::Ten, Rank::Jack, Rank::Queen],
+                vec![Suit::Hearts, Suit::Diamonds, Suit::Clubs, Suit::Spades],
+                1
+            ));
+            let value = hand.value();
+            assert_eq!(value.category, HandRank::Straight);
+            assert_eq!(value.tiebreak, vec![Rank::King]);
+        }
+
+        #[test]
+        fn test_wheel_is_five_high() {
+            let hand = Hand::new(create_test_cards(
+                vec![Rank::Ace, Rank::Two, Rank::Three, Rank::Four, Rank::Five],
+                vec![Suit::Hearts, Suit::Diamonds, Suit::Clubs, Suit::Spades, Suit::Hearts],
+                0
+            ));
+            assert_eq!(hand.value().tiebreak, vec![Rank::Five]);
+        }
+    }
+
+    mod combination_tests {
+        use super::*;
+
+        #[test]
+        fn test_combination_counts() {
+            assert_eq!(combinations(5, 5), vec![vec![0, 1, 2, 3, 4]]);
+            assert_eq!(combinations(7, 5).len(), 21);
+            assert_eq!(combinations(9, 5).len(), 126);
+            assert!(combinations(4, 5).is_empty());
+        }
+    }
+}
@@ -0,0 +1,44 @@
+use crate::card::Card;
+use crate::hand::{Hand, HandEvaluation, HandValue};
+use crate::variant::{Step, Variant};
+
+// Texas Hold'em with the game's jokers shuffled in as wild cards: two hole
+// cards each, then the flop, turn and river on the board with a betting
+// round before each and after the river. A hand is the best five of the
+// player's two cards and the five on the board.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct HoldEm;
+
+impl HoldEm {
+    // The hole cards followed by the board, in the order evaluations refer to
+    fn cards(hole: &Hand, board: &[Card]) -> Hand {
+        Hand::new(hole.cards.iter().chain(board).copied().collect())
+    }
+}
+
+impl Variant for HoldEm {
+    fn name(&self) -> &'static str {
+        "holdem"
+    }
+
+    fn steps(&self) -> Vec<Step> {
+        vec![
+            Step::Deal { count: 2 },
+            Step::Betting,
+            Step::Board { count: 3 },  // The flop
+            Step::Betting,
+            Step::Board { count: 1 },  // The turn
+            Step::Betting,
+            Step::Board { count: 1 },  // The river
+            Step::Betting,
+        ]
+    }
+
+    fn evaluate(&self, hole: &Hand, board: &[Card]) -> HandEvaluation {
+        Self::cards(hole, board).evaluation()
+    }
+
+    fn value(&self, hole: &Hand, board: &[Card]) -> HandValue {
+        Self::cards(hole, board).value()
+    }
+}
@@ -1,13 +1,33 @@
 // src/lib.rs
+pub mod action;
 pub mod card;
+pub mod card_set;
+pub mod controller;
 pub mod deck;
 pub mod hand;
+pub mod holdem;
+pub mod omaha;
 pub mod player;
+pub mod stud;
 pub mod game;
+pub mod error;
+pub mod event;
+pub mod variant;
+#[cfg(feature = "lookup")]
+pub mod lookup;
 
 // Re-export the types that tests need to use
-pub use card::{Card, Rank, Suit};
+pub use action::Action;
+pub use card::{Card, JokerId, Rank, Suit};
+pub use card_set::CardSet;
+pub use controller::{PlayerController, SeatView, TableView};
 pub use deck::Deck;
-pub use hand::{Hand, HandRank};
+pub use error::{GameError, GameResult};
+pub use event::{EventSink, ForcedBet, GameEvent};
+pub use hand::{Hand, HandEvaluation, HandRank, HandValue, JokerAssignment};
+pub use holdem::HoldEm;
+pub use omaha::Omaha;
 pub use player::Player;
-pub use game::Game;
\ No newline at end of file
+pub use stud::SevenCardStud;
+pub use game::{ForcedBets, Game};
+pub use variant::{BetLimit, JokerDraw, Step, Variant};
\ No newline at end of file
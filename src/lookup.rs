@@ -0,0 +1,280 @@
+// Table-driven hand evaluator for hot loops such as simulations.
+//
+// Cards are encoded as a single byte (`rank * 4 + suit`, with 52 for a joker)
+// and hands are scored into a packed u32 that orders exactly like HandValue:
+// the category in bits 20..24 followed by five 4-bit tie-break slots holding
+// `rank + 1`, most significant first, with 0 marking an unused slot.
+//
+// Joker handling mirrors Hand::evaluation, so both evaluators always agree.
+use std::sync::OnceLock;
+use crate::card::{Card, Rank, Suit};
+use crate::hand::{HandRank, HandValue};
+
+pub const JOKER: u8 = 52;
+
+const ALL_RANKS: u16 = 0x1FFF;
+const ACE: u8 = 12;
+
+const CATEGORIES: [HandRank; 11] = [
+    HandRank::HighCard,
+    HandRank::OnePair,
+    HandRank::TwoPair,
+    HandRank::ThreeOfAKind,
+    HandRank::Straight,
+    HandRank::Flush,
+    HandRank::FullHouse,
+    HandRank::FourOfAKind,
+    HandRank::StraightFlush,
+    HandRank::RoyalFlush,
+    HandRank::FiveOfAKind,
+];
+
+pub fn encode(card: &Card) -> u8 {
+    // Every joker shares one code
+    card.index().min(JOKER)
+}
+
+pub fn decode(code: u8) -> Card {
+    // Every joker encodes the same, so they all decode as joker 0
+    if code >= JOKER {
+        return Card::joker(0);
+    }
+    let rank = Rank::from_u8(code / 4).unwrap_or(Rank::Ace);
+    Card::new(rank, Suit::ALL[(code % 4) as usize])
+}
+
+// Scores any number of cards; hands of more than five are scored by their
+// best five.
+pub fn evaluate(cards: &[Card]) -> HandValue {
+    unpack(evaluate_packed(cards))
+}
+
+// As `evaluate`, but packed and without allocating. Hands of up to nine
+// cards (Omaha's hole cards and board) are encoded on the stack.
+pub fn evaluate_packed(cards: &[Card]) -> u32 {
+    let mut buffer = [0u8; 9];
+    if cards.len() > buffer.len() {
+        let codes: Vec<u8> = cards.iter().map(encode).collect();
+        return evaluate_codes(&codes);
+    }
+    for (slot, card) in buffer.iter_mut().zip(cards) {
+        *slot = encode(card);
+    }
+    evaluate_codes(&buffer[..cards.len()])
+}
+
+pub fn evaluate_codes(codes: &[u8]) -> u32 {
+    let n = codes.len();
+    if n <= 5 {
+        return evaluate_five(codes);
+    }
+    let mut best = 0;
+    for a in 0..n - 4 {
+        for b in a + 1..n - 3 {
+            for c in b + 1..n - 2 {
+                for d in c + 1..n - 1 {
+                    for e in d + 1..n {
+                        best = best.max(evaluate_five(&[codes[a], codes[b], codes[c], codes[d], codes[e]]));
+                    }
+                }
+            }
+        }
+    }
+    best
+}
+
+// HandValue owns its tie-break ranks, so this allocates once; loops that
+// only compare hands can compare the packed values instead.
+pub fn unpack(packed: u32) -> HandValue {
+    let category = CATEGORIES[(packed >> 20) as usize];
+    let mut tiebreak = Vec::with_capacity(5);
+    for slot in 0..5 {
+        let nibble = ((packed >> (16 - 4 * slot)) & 0xF) as u8;
+        match nibble.checked_sub(1).and_then(Rank::from_u8) {
+            Some(rank) => tiebreak.push(rank),
+            None => break,
+        }
+    }
+    HandValue { category, tiebreak }
+}
+
+// Best straight top (plus one) for every 13-bit rank mask, or 0 if the ranks
+// don't fit inside any five-rank window. With distinct ranks in a five-card
+// hand the jokers fill exactly the gaps, so the mask alone decides.
+fn straight_table() -> &'static [u8] {
+    static TABLE: OnceLock<Vec<u8>> = OnceLock::new();
+    TABLE.get_or_init(|| {
+        let windows: Vec<(u8, u16)> = (3..=ACE)
+            .rev()
+            .map(|top| {
+                let window = if top == 3 {
+                    0b1_0000_0000_1111 // A-2-3-4-5
+                } else {
+                    0b1_1111 << (top - 4)
+                };
+                (top, window)
+            })
+            .collect();
+        (0..=ALL_RANKS)
+            .map(|mask| {
+                windows.iter()
+                    .find(|&&(_, window)| mask & !window == 0)
+                    .map_or(0, |&(top, _)| top + 1)
+            })
+            .collect()
+    })
+}
+
+fn highest(mask: u16) -> u8 {
+    15 - mask.leading_zeros() as u8
+}
+
+struct Packer {
+    packed: u32,
+    slot: u32,
+}
+
+impl Packer {
+    fn new(category: HandRank) -> Self {
+        Packer { packed: (category as u32) << 20, slot: 0 }
+    }
+
+    fn push(&mut self, rank: u8) -> &mut Self {
+        self.packed |= (rank as u32 + 1) << (16 - 4 * self.slot);
+        self.slot += 1;
+        self
+    }
+
+    // Pushes every rank in `mask`, highest first
+    fn push_mask(&mut self, mut mask: u16) -> &mut Self {
+        while mask != 0 {
+            let rank = highest(mask);
+            self.push(rank);
+            mask &= !(1 << rank);
+        }
+        self
+    }
+
+    fn finish(&self) -> u32 {
+        self.packed
+    }
+}
+
+fn evaluate_five(codes: &[u8]) -> u32 {
+    let total = codes.len();
+    let mut jokers = 0;
+    let mut suits = 0u8;
+    // seen[k] holds the ranks that appear more than k times
+    let mut seen = [0u16; 4];
+    for &code in codes {
+        if code >= JOKER {
+            jokers += 1;
+            continue;
+        }
+        let bit = 1 << (code >> 2);
+        seen[3] |= seen[2] & bit;
+        seen[2] |= seen[1] & bit;
+        seen[1] |= seen[0] & bit;
+        seen[0] |= bit;
+        suits |= 1 << (code & 3);
+    }
+    let naturals = seen[0];
+    // Ranks that the jokers can bring up to `size` of a kind
+    let reachable = |size: usize| -> u16 {
+        if total < size {
+            0
+        } else if size <= jokers {
+            ALL_RANKS
+        } else {
+            seen[size - jokers - 1]
+        }
+    };
+
+    if total == 5 && naturals.count_ones() <= 1 {
+        let rank = if naturals == 0 { ACE } else { highest(naturals) };
+        return Packer::new(HandRank::FiveOfAKind).push(rank).finish();
+    }
+
+    let is_flush = total == 5 && suits.count_ones() == 1;
+    let straight = if total == 5 && seen[1] == 0 {
+        straight_table()[naturals as usize]
+    } else {
+        0
+    };
+    if is_flush && straight == ACE + 1 {
+        return Packer::new(HandRank::RoyalFlush).push(ACE).finish();
+    }
+    if is_flush && straight != 0 {
+        return Packer::new(HandRank::StraightFlush).push(straight - 1).finish();
+    }
+
+    let quads = reachable(4);
+    if quads != 0 {
+        let rank = highest(quads);
+        let mut packer = Packer::new(HandRank::FourOfAKind);
+        packer.push(rank);
+        let kickers = naturals & !(1 << rank);
+        if kickers != 0 {
+            packer.push_mask(kickers);
+        } else if total == 5 {
+            packer.push(if rank == ACE { ACE - 1 } else { ACE });
+        }
+        return packer.finish();
+    }
+
+    if total == 5 {
+        // Three plus two naturally, or two pairs plus a joker
+        let full_house = match jokers {
+            0 if seen[2] != 0 && seen[1] & !seen[2] != 0 => Some((highest(seen[2]), highest(seen[1] & !seen[2]))),
+            1 if seen[1].count_ones() == 2 => {
+                let trips = highest(seen[1]);
+                Some((trips, highest(seen[1] & !(1 << trips))))
+            }
+            _ => None,
+        };
+        if let Some((trips, pair)) = full_house {
+            return Packer::new(HandRank::FullHouse).push(trips).push(pair).finish();
+        }
+    }
+
+    if is_flush {
+        // Jokers play as aces of the flush suit
+        let mut packer = Packer::new(HandRank::Flush);
+        for _ in 0..jokers {
+            packer.push(ACE);
+        }
+        return packer.push_mask(naturals).finish();
+    }
+
+    if straight != 0 {
+        return Packer::new(HandRank::Straight).push(straight - 1).finish();
+    }
+
+    let trips = reachable(3);
+    if trips != 0 {
+        let rank = highest(trips);
+        return Packer::new(HandRank::ThreeOfAKind).push(rank).push_mask(naturals & !(1 << rank)).finish();
+    }
+
+    if jokers == 0 && seen[1].count_ones() >= 2 {
+        let high = highest(seen[1]);
+        let low = highest(seen[1] & !(1 << high));
+        return Packer::new(HandRank::TwoPair)
+            .push(high)
+            .push(low)
+            .push_mask(naturals & !(1 << high) & !(1 << low))
+            .finish();
+    }
+
+    let pair = reachable(2);
+    if pair != 0 {
+        let rank = highest(pair);
+        return Packer::new(HandRank::OnePair).push(rank).push_mask(naturals & !(1 << rank)).finish();
+    }
+
+    let mut packer = Packer::new(HandRank::HighCard);
+    for _ in 0..jokers {
+        packer.push(ACE);
+    }
+    packer.push_mask(naturals).finish()
+}
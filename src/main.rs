@@ -1,42 +1,164 @@
-mod card;
-mod deck;
-mod hand;
-mod player;
-mod game;
-
 use std::io::{self, Write};
-pub use game::Game;
+use joker_poker::game::{MAX_DRAW, MAX_DRAW_WITH_ACE};
+use joker_poker::{Action, BetLimit, Card, EventSink, ForcedBet, ForcedBets, Game, GameError, GameEvent, Hand, HoldEm};
+use joker_poker::{JokerDraw, Omaha, PlayerController, SevenCardStud, TableView, Variant};
+
+// Written after every round and offered for resuming at startup
+#[cfg(feature = "serde")]
+const SAVE_PATH: &str = "joker_poker_save.json";
+
+// Asks whoever is at the keyboard for the decisions of one seat
+struct TerminalController;
+
+impl PlayerController for TerminalController {
+    fn choose_action(&mut self, view: &TableView) -> Action {
+        show_player_status(view);
+        println!("Current bet: {} (to call: {})", view.current_bet, view.to_call);
+        println!("1: Call/Check");
+        println!("2: Raise");
+        println!("3: Fold");
+        let seat = &view.seats[view.player];
+        let pot_limited = view.max_bet < seat.street_bet + seat.chips;
+        if pot_limited {
+            println!("4: Raise the pot ({})", view.max_bet);
+        } else {
+            println!("4: All in");
+        }
+
+        match get_number_input("Enter your choice (1..4): ", 1..=4) {
+            1 if view.to_call == 0 => Action::Check,
+            1 => Action::Call,
+            2 => {
+                let min_raise = view.min_raise.min(view.max_bet);
+                let prompt = format!("Raise to ({}..{}): ", min_raise, view.max_bet);
+                Action::Raise(get_number_input(&prompt, min_raise..=view.max_bet))
+            }
+            3 => Action::Fold,
+            _ if pot_limited => Action::Raise(view.max_bet),
+            _ => Action::AllIn,
+        }
+    }
+
+    fn choose_discards(&mut self, view: &TableView) -> Vec<usize> {
+        show_player_status(view);
+        let Some(hand) = view.hand else {
+            return Vec::new();
+        };
+        for (i, card) in hand.cards.iter().enumerate() {
+            println!("{}: {:#}", i + 1, card);
+        }
+        println!("Cards to discard (e.g. \"1 3 4\" or \"Kd 9s\", up to {}, or {} when keeping an Ace; Enter to stand pat):",
+            MAX_DRAW, MAX_DRAW_WITH_ACE);
+        loop {
+            match get_discard_choice(hand) {
+                Ok(discards) => return discards,
+                Err(err) => println!("{}, try again", err),
+            }
+        }
+    }
+}
+
+// Prints the table's events, naming players by seat
+struct TerminalOutput {
+    names: Vec<String>,
+}
+
+impl EventSink for TerminalOutput {
+    fn emit(&mut self, event: GameEvent) {
+        match event {
+            GameEvent::RoundStarted { seed, dealer } => {
+                println!("\n=== Starting New Round (seed {}) ===", seed);
+                println!("{} has the button", self.names[dealer]);
+            }
+            GameEvent::Posted { player, bet, amount, all_in } => {
+                let bet = match bet {
+                    ForcedBet::Ante => "an ante",
+                    ForcedBet::SmallBlind => "the small blind",
+                    ForcedBet::BigBlind => "the big blind",
+                    ForcedBet::BringIn => "the bring-in",
+                };
+                let all_in = if all_in { " and is all in" } else { "" };
+                println!("{} posts {} of {}{}", self.names[player], bet, amount, all_in);
+            }
+            GameEvent::BettingStarted { final_round: true } => println!("\n=== Final Betting Round ==="),
+            GameEvent::BettingStarted { final_round: false } => println!("\n=== Betting Round ==="),
+            GameEvent::Checked { player } => println!("{} checks", self.names[player]),
+            GameEvent::Called { player, amount } => println!("{} calls {}", self.names[player], amount),
+            GameEvent::Raised { player, to } => println!("{} raises to {}", self.names[player], to),
+            GameEvent::Folded { player } => println!("{} folds", self.names[player]),
+            GameEvent::AllIn { player, amount } => println!("{} is all in for {}", self.names[player], amount),
+            GameEvent::BoardDealt { cards } => println!("Board: {:#}", Hand::new(cards)),
+            GameEvent::UpCardsDealt { player, cards } => println!("{} is dealt {:#} face up", self.names[player], Hand::new(cards)),
+            GameEvent::DrawingStarted => println!("\n=== Drawing Round ==="),
+            GameEvent::Drew { player, count: 0 } => println!("{} stands pat", self.names[player]),
+            GameEvent::Drew { player, count } => println!("{} draws {} cards", self.names[player], count),
+            GameEvent::ActionRejected { reason, .. } => println!("{}", reason),
+            GameEvent::ShowdownStarted => println!("\n=== Showdown ==="),
+            GameEvent::HandShown { player, cards, evaluation } => {
+                // With a board the best five differ from the player's own cards
+                let best = (evaluation.cards != cards).then(|| Hand::new(evaluation.cards.clone()));
+                println!("{}'s hand: {:#}", self.names[player], Hand::new(cards));
+                match best {
+                    Some(best) => println!("{:?}: {:#}", evaluation.value.category, best),
+                    None => println!("{:?}", evaluation.value.category),
+                }
+                for joker in &evaluation.jokers {
+                    println!("  {}", joker);
+                }
+            }
+            GameEvent::PotAwarded { pot, player, amount } => {
+                let label = if pot == 0 { "the main pot".to_string() } else { format!("side pot {}", pot) };
+                println!("{} wins {} chips from {}!", self.names[player], amount, label);
+            }
+            GameEvent::RoundOver => {}
+        }
+    }
+}
 
 fn main() {
+    let seed = match parse_seed(std::env::args().skip(1)) {
+        Ok(seed) => seed,
+        Err(err) => {
+            eprintln!("{}", err);
+            eprintln!("Usage: joker_poker [--seed <number>]");
+            std::process::exit(2);
+        }
+    };
+
     println!("Welcome to Joker Poker!");
-    
-    // Get number of players
-    let num_players = get_number_input("Enter number of players (2-6): ", 2..=6);
-    
-    // Get number of jokers
-    let num_jokers = get_number_input("Enter number of jokers (0-4): ", 0..=4);
-    
-    // Get player names
-    let mut player_names = Vec::new();
-    for i in 1..=num_players {
-        print!("Enter name for Player {}: ", i);
-        io::stdout().flush().unwrap();
-        let mut name = String::new();
-        io::stdin().read_line(&mut name).unwrap();
-        player_names.push(name.trim().to_string());
-    }
-    
-    // Initialize game with specified number of jokers
-    let mut game = Game::new(
-        player_names,
-        1000,     // Starting chips
-        10,       // Min bet
-        num_jokers
-    );
-    
+
+    let mut game = match offer_resume() {
+        Some(game) => {
+            if seed.is_some() {
+                println!("--seed only applies to new games; the saved game keeps its own");
+            }
+            game
+        }
+        None => new_game(seed),
+    };
+    println!("Game seed: {} (replay with --seed {})", game.get_seed(), game.get_seed());
+
+    let num_players = game.get_players().len();
+    let mut controllers: Vec<Box<dyn PlayerController>> = (0..num_players)
+        .map(|_| Box::new(TerminalController) as Box<dyn PlayerController>)
+        .collect();
+    let mut output = TerminalOutput {
+        names: game.get_players().iter().map(|p| p.name.clone()).collect(),
+    };
+
     // Main game loop
     loop {
-        game.start_round();
+        // A loaded game may stop part way through a round
+        let played = if game.is_round_active() {
+            game.resume_round(&mut controllers, &mut output)
+        } else {
+            game.start_round(&mut controllers, &mut output)
+        };
+        if let Err(err) = played {
+            println!("{}", err);
+            break;
+        }
+        save_game(&game);
         
         while !game.is_game_over() {
             std::thread::sleep(std::time::Duration::from_millis(100));
@@ -59,18 +181,112 @@ fn main() {
         
         if input.trim().to_lowercase() == "y" {
             let new_num_jokers = get_number_input("Enter new number of jokers (0-4): ", 0..=4);
-            game = Game::new(
-                game.get_players().iter().map(|p| p.name.clone()).collect(),
-                game.get_players()[0].chips,
-                game.get_min_bet(),
-                new_num_jokers
-            );
+            if let Err(err) = game.set_num_jokers(new_num_jokers) {
+                println!("{}", err);
+            }
+            save_game(&game);
         }
     }
     
     println!("Thanks for playing!");
 }
 
+// Sets up a new game from the players' answers
+fn new_game(seed: Option<u64>) -> Game {
+    // Get number of players
+    let num_players = get_number_input("Enter number of players (2-6): ", 2..=6);
+    
+    // Get number of jokers
+    let num_jokers = get_number_input("Enter number of jokers (0-4): ", 0..=4);
+
+    println!("1: Joker Draw");
+    println!("2: Texas Hold'em");
+    println!("3: Omaha");
+    println!("4: Pot-limit Omaha");
+    println!("5: Seven-Card Stud");
+    let variant: Box<dyn Variant> = match get_number_input("Choose a game (1..5): ", 1..=5) {
+        1 => Box::new(JokerDraw),
+        2 => Box::new(HoldEm),
+        3 => Box::new(Omaha::new(BetLimit::NoLimit)),
+        4 => Box::new(Omaha::new(BetLimit::PotLimit)),
+        _ => Box::new(SevenCardStud),
+    };
+    // Stud is played with antes and a bring-in instead of blinds
+    let forced_bets = if variant.name() == SevenCardStud.name() {
+        ForcedBets { ante: 1, bring_in: 5, ..ForcedBets::default() }
+    } else {
+        ForcedBets { small_blind: 5, big_blind: 10, ..ForcedBets::default() }
+    };
+    
+    // Get player names
+    let mut player_names = Vec::new();
+    for i in 1..=num_players {
+        print!("Enter name for Player {}: ", i);
+        io::stdout().flush().unwrap();
+        let mut name = String::new();
+        io::stdin().read_line(&mut name).unwrap();
+        player_names.push(name.trim().to_string());
+    }
+    
+    // Initialize game with specified number of jokers
+    let mut game = Game::new(
+        player_names,
+        1000,     // Starting chips
+        10,       // Min bet
+        num_jokers
+    ).expect("at most 4 jokers can be chosen");
+    game.set_variant(variant).expect("no round has started");
+    game.set_forced_bets(forced_bets).expect("the small blind is below the big blind");
+    if let Some(seed) = seed {
+        game.set_seed(seed);
+    }
+    game
+}
+
+// Offers to pick up the last saved session, if there is one
+#[cfg(feature = "serde")]
+fn offer_resume() -> Option<Game> {
+    if !std::path::Path::new(SAVE_PATH).exists() {
+        return None;
+    }
+    print!("Resume the saved game? (y/n): ");
+    io::stdout().flush().unwrap();
+    let mut input = String::new();
+    io::stdin().read_line(&mut input).unwrap();
+    if input.trim().to_lowercase() != "y" {
+        return None;
+    }
+
+    match Game::load(SAVE_PATH) {
+        Ok(game) => {
+            for player in game.get_players() {
+                println!("{}: {} chips", player.name, player.chips);
+            }
+            Some(game)
+        }
+        Err(err) => {
+            println!("Could not load {}: {}", SAVE_PATH, err);
+            None
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+fn save_game(game: &Game) {
+    if let Err(err) = game.save(SAVE_PATH) {
+        println!("Could not save the game: {}", err);
+    }
+}
+
+// Built without the `serde` feature there is nothing to resume or save to
+#[cfg(not(feature = "serde"))]
+fn offer_resume() -> Option<Game> {
+    None
+}
+
+#[cfg(not(feature = "serde"))]
+fn save_game(_game: &Game) {}
+
 fn get_number_input(prompt: &str, range: std::ops::RangeInclusive<u32>) -> u32 {
     loop {
         print!("{}", prompt);
@@ -86,4 +302,75 @@ fn get_number_input(prompt: &str, range: std::ops::RangeInclusive<u32>) -> u32 {
         }
         println!("Invalid input, try again");
     }
-}
\ No newline at end of file
+}
+fn show_player_status(view: &TableView) {
+    let seat = &view.seats[view.player];
+    println!("\n{}'s turn", seat.name);
+    println!("Chips: {}", seat.chips);
+    if view.dealer == view.player {
+        println!("You have the button");
+    }
+    if let Some(hand) = view.hand {
+        println!("Hand: {:#}", hand);
+        let showing = hand.visible();
+        if !showing.cards.is_empty() {
+            println!("Showing: {:#}", showing);
+        }
+    }
+    if !view.board.is_empty() {
+        println!("Board: {:#}", Hand::new(view.board.to_vec()));
+    }
+    for (i, seat) in view.seats.iter().enumerate() {
+        if i != view.player && !seat.up_cards.is_empty() {
+            println!("{} shows {:#}", seat.name, Hand::new(seat.up_cards.clone()));
+        }
+    }
+}
+
+// Reads 1-based card positions or cards in notation, returned as 0-based
+// indices into the hand
+fn get_discard_choice(hand: &Hand) -> Result<Vec<usize>, String> {
+    print!("Discard: ");
+    io::stdout().flush().unwrap();
+
+    let mut input = String::new();
+    io::stdin().read_line(&mut input).unwrap();
+
+    let mut discards: Vec<usize> = Vec::new();
+    for part in input.split_whitespace() {
+        let position = match part.parse::<usize>() {
+            Ok(n) if n > 0 => n - 1,
+            Ok(_) => return Err("Card positions start at 1".to_string()),
+            Err(_) => {
+                let card: Card = part.parse().map_err(|err: GameError| err.to_string())?;
+                // The first matching card not already chosen; a bare "Jk"
+                // matches any joker, "Jk2" only that one
+                let any_joker = part.eq_ignore_ascii_case("jk");
+                hand.cards.iter()
+                    .enumerate()
+                    .position(|(i, held)| {
+                        !discards.contains(&i) && (*held == card || any_joker && held.is_joker())
+                    })
+                    .ok_or_else(|| format!("{} is not in your hand", card))?
+            }
+        };
+        discards.push(position);
+    }
+    Ok(discards)
+}
+
+// Accepts `--seed N` or `--seed=N`
+fn parse_seed(mut args: impl Iterator<Item = String>) -> Result<Option<u64>, String> {
+    let mut seed = None;
+    while let Some(arg) = args.next() {
+        let value = if arg == "--seed" {
+            args.next().ok_or("--seed needs a value")?
+        } else if let Some(value) = arg.strip_prefix("--seed=") {
+            value.to_string()
+        } else {
+            return Err(format!("Unknown argument: {}", arg));
+        };
+        seed = Some(value.parse::<u64>().map_err(|_| format!("Invalid seed: {}", value))?);
+    }
+    Ok(seed)
+}
@@ -0,0 +1,56 @@
+use crate::card::Card;
+use crate::hand::{Hand, HandEvaluation};
+use crate::variant::{BetLimit, Step, Variant};
+
+// Omaha with the game's jokers as wild cards: dealt and bet like Hold'em,
+// but with four hole cards of which a hand must use exactly two, together
+// with exactly three from the board.
+#[derive(Debug, Clone, Copy)]
+pub struct Omaha {
+    limit: BetLimit,
+}
+
+impl Omaha {
+    pub fn new(limit: BetLimit) -> Self {
+        Omaha { limit }
+    }
+}
+
+impl Default for Omaha {
+    fn default() -> Self {
+        Omaha::new(BetLimit::NoLimit)
+    }
+}
+
+impl Variant for Omaha {
+    fn name(&self) -> &'static str {
+        match self.limit {
+            BetLimit::NoLimit => "omaha",
+            BetLimit::PotLimit => "pot-limit-omaha",
+        }
+    }
+
+    fn steps(&self) -> Vec<Step> {
+        vec![
+            Step::Deal { count: 4 },
+            Step::Betting,
+            Step::Board { count: 3 },
+            Step::Betting,
+            Step::Board { count: 1 },
+            Step::Betting,
+            Step::Board { count: 1 },
+            Step::Betting,
+        ]
+    }
+
+    fn evaluate(&self, hole: &Hand, board: &[Card]) -> HandEvaluation {
+        let cards = Hand::new(hole.cards.iter().chain(board).copied().collect());
+        // Only a player everyone else folded to shows down before the flop,
+        // and their hand is never compared
+        cards.best_five_using(hole.cards.len(), 2).unwrap_or_else(|| cards.evaluation())
+    }
+
+    fn bet_limit(&self) -> BetLimit {
+        self.limit
+    }
+}
@@ -1,5 +1,7 @@
+use crate::error::{GameError, GameResult};
 use crate::hand::Hand;
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Player {
     pub name: String,
     pub chips: u32,
@@ -21,23 +23,30 @@ impl Player {
         }
     }
 
-    pub fn place_bet(&mut self, amount: u32) -> Result<u32, &'static str> {
-        if amount > self.chips {
-            return Err("Insufficient chips");
-        }
+    pub fn place_bet(&mut self, amount: u32) -> GameResult<u32> {
+        self.check_chips(amount)?;
         self.chips -= amount;
         Ok(amount)
     }
 
 
+    fn check_chips(&self, amount: u32) -> GameResult<()> {
+        if amount > self.chips {
+            return Err(GameError::InsufficientChips { needed: amount, available: self.chips });
+        }
+        Ok(())
+    }
+
+    pub fn is_all_in(&self) -> bool {
+        self.in_round && self.chips == 0
+    }
+
     pub fn add_chips(&mut self, amount: u32) {
         self.chips += amount;
     }
 
-    pub fn remove_chips(&mut self, amount: u32) -> Result<(), &'static str> {
-        if amount > self.chips {
-            return Err("Insufficient chips");
-        }
+    pub fn remove_chips(&mut self, amount: u32) -> GameResult<()> {
+        self.check_chips(amount)?;
         self.chips -= amount;
         Ok(())
     }
@@ -0,0 +1,77 @@
+use crate::card::{Card, Rank, Suit};
+use crate::hand::{Hand, HandEvaluation, HandValue};
+use crate::variant::{Step, Variant};
+
+// Seven-card stud with the game's jokers as wild cards. Each player gets two
+// cards down and one up, then three more up and a last one down, with a
+// betting round after each deal. The lowest up-card posts the bring-in; on
+// later streets the best hand showing acts first. A hand is the best five of
+// the player's seven cards.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SevenCardStud;
+
+impl Variant for SevenCardStud {
+    fn name(&self) -> &'static str {
+        "seven-card-stud"
+    }
+
+    fn steps(&self) -> Vec<Step> {
+        vec![
+            Step::Deal { count: 2 },
+            Step::DealFaceUp { count: 1 },  // Third street
+            Step::Betting,
+            Step::DealFaceUp { count: 1 },  // Fourth street
+            Step::Betting,
+            Step::DealFaceUp { count: 1 },  // Fifth street
+            Step::Betting,
+            Step::DealFaceUp { count: 1 },  // Sixth street
+            Step::Betting,
+            Step::Deal { count: 1 },        // The river, face down
+            Step::Betting,
+        ]
+    }
+
+    // Ties for the lowest card go by suit, clubs lowest, then diamonds,
+    // hearts and spades; ties for the best hand showing to the first of them
+    // after the button. A joker showing is never the lowest card.
+    fn first_to_act(&self, street: usize, hands: &[(usize, &Hand)]) -> Option<usize> {
+        if street == 0 {
+            hands.iter()
+                .filter_map(|&(seat, hand)| {
+                    hand.visible().cards.iter().map(bring_in_order).min().map(|order| (order, seat))
+                })
+                .min()
+                .map(|(_, seat)| seat)
+        } else {
+            hands.iter()
+                .map(|&(seat, hand)| (hand.visible_value(), seat))
+                .reduce(|best, candidate| if candidate.0 > best.0 { candidate } else { best })
+                .map(|(_, seat)| seat)
+        }
+    }
+
+    fn evaluate(&self, hole: &Hand, _board: &[Card]) -> HandEvaluation {
+        hole.evaluation()
+    }
+
+    fn value(&self, hole: &Hand, _board: &[Card]) -> HandValue {
+        hole.value()
+    }
+}
+
+// Sorts up-cards for the bring-in, lowest first. This suit order is the
+// usual one for stud and differs from Suit's own; jokers sort last.
+fn bring_in_order(card: &Card) -> (bool, Option<(Rank, u8)>) {
+    match card.natural() {
+        Some((rank, suit)) => {
+            let suit = match suit {
+                Suit::Clubs => 0,
+                Suit::Diamonds => 1,
+                Suit::Hearts => 2,
+                Suit::Spades => 3,
+            };
+            (false, Some((rank, suit)))
+        }
+        None => (true, None),
+    }
+}
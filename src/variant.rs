@@ -0,0 +1,162 @@
+use crate::card::{Card, Rank};
+use crate::error::{GameError, GameResult};
+use crate::hand::{Hand, HandEvaluation, HandValue};
+use crate::holdem::HoldEm;
+use crate::omaha::Omaha;
+use crate::stud::SevenCardStud;
+
+// Players may draw up to three cards, or four when keeping an Ace
+pub const MAX_DRAW: usize = 3;
+pub const MAX_DRAW_WITH_ACE: usize = 4;
+
+// One step of a round. A variant lists its steps in the order they are
+// played; the showdown follows the last one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Step {
+    // Every player in the round gets `count` more cards of their own
+    Deal { count: usize },
+    // As Deal, but turned face up for everyone to see
+    DealFaceUp { count: usize },
+    // `count` shared cards are turned up on the board
+    Board { count: usize },
+    Betting,
+    // Every player in the round may exchange cards, within `validate_draw`
+    Draw,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BetLimit {
+    NoLimit,
+    // A raise may at most add the size of the pot after calling
+    PotLimit,
+}
+
+// The rules of one kind of poker. `Game` handles the chips, the turn order
+// and the forced bets, and asks the variant what to deal, when to bet and
+// draw, and how to score the hands. Variants are shared along with the
+// `Game` that holds them, so they must be `Send + Sync`.
+pub trait Variant: Send + Sync {
+    // Saved games record their variant by this name, so it must be unique.
+    // Only variants that `by_name` knows can be saved.
+    fn name(&self) -> &'static str;
+
+    fn steps(&self) -> Vec<Step>;
+
+    // The seat that opens betting round `street` (counted from 0), picked
+    // from the hands of the players still in, which are listed in turn
+    // order from the seat after the button. On the first street this seat
+    // posts the bring-in. None leaves it to the seat after the button.
+    fn first_to_act(&self, _street: usize, _hands: &[(usize, &Hand)]) -> Option<usize> {
+        None
+    }
+
+    // Checks the positions a player wants to throw away during a draw
+    fn validate_draw(&self, _hand: &Hand, _discards: &[usize]) -> GameResult<()> {
+        Err(GameError::InvalidDraw(format!("there is no draw in {}", self.name())))
+    }
+
+    // Scores a player's own cards together with the board. Joker positions
+    // in the result count the hole cards first, then the board.
+    fn evaluate(&self, hole: &Hand, board: &[Card]) -> HandEvaluation;
+
+    // Used to award the pots; only the value is needed there
+    fn value(&self, hole: &Hand, board: &[Card]) -> HandValue {
+        self.evaluate(hole, board).value
+    }
+
+    fn bet_limit(&self) -> BetLimit {
+        BetLimit::NoLimit
+    }
+}
+
+// The built-in variant with the given name
+pub fn by_name(name: &str) -> Option<Box<dyn Variant>> {
+    match name {
+        "joker-draw" => Some(Box::new(JokerDraw)),
+        "holdem" => Some(Box::new(HoldEm)),
+        "omaha" => Some(Box::new(Omaha::new(BetLimit::NoLimit))),
+        "pot-limit-omaha" => Some(Box::new(Omaha::new(BetLimit::PotLimit))),
+        "seven-card-stud" => Some(Box::new(SevenCardStud)),
+        _ => None,
+    }
+}
+
+// Five-card draw with jokers: one betting round, a draw of up to three
+// cards (four when keeping an Ace), and a final betting round.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct JokerDraw;
+
+impl JokerDraw {
+    pub fn max_discards(hand: &Hand, discards: &[usize]) -> usize {
+        let keeps_ace = hand.cards.iter()
+            .enumerate()
+            .any(|(i, card)| !discards.contains(&i) && card.rank() == Some(Rank::Ace));
+        if keeps_ace { MAX_DRAW_WITH_ACE } else { MAX_DRAW }
+    }
+}
+
+impl Variant for JokerDraw {
+    fn name(&self) -> &'static str {
+        "joker-draw"
+    }
+
+    fn steps(&self) -> Vec<Step> {
+        vec![Step::Deal { count: 5 }, Step::Betting, Step::Draw, Step::Betting]
+    }
+
+    fn validate_draw(&self, hand: &Hand, discards: &[usize]) -> GameResult<()> {
+        if let Some(&i) = discards.iter().find(|&&i| i >= hand.cards.len()) {
+            return Err(GameError::InvalidDraw(format!("no card at position {}", i + 1)));
+        }
+        if (1..discards.len()).any(|i| discards[..i].contains(&discards[i])) {
+            return Err(GameError::InvalidDraw("the same card was chosen twice".to_string()));
+        }
+        let limit = Self::max_discards(hand, discards);
+        if discards.len() > limit {
+            let reason = if limit == MAX_DRAW && discards.len() == MAX_DRAW_WITH_ACE {
+                format!("drawing {} cards requires keeping an Ace", MAX_DRAW_WITH_ACE)
+            } else {
+                format!("at most {} cards may be drawn", limit)
+            };
+            return Err(GameError::InvalidDraw(reason));
+        }
+        Ok(())
+    }
+
+    fn evaluate(&self, hole: &Hand, _board: &[Card]) -> HandEvaluation {
+        hole.evaluation()
+    }
+
+    // Goes through Hand::value so the `lookup` evaluator is used when enabled
+    fn value(&self, hole: &Hand, _board: &[Card]) -> HandValue {
+        hole.value()
+    }
+}
+
+// Saves a game's variant as its name and finds it again by that name. A
+// variant `by_name` can't find is refused when saving, since the save could
+// never be loaded.
+#[cfg(feature = "serde")]
+pub(crate) mod serde_by_name {
+    use super::{by_name, Variant};
+    use serde::{de, ser};
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    #[allow(clippy::borrowed_box)]
+    pub fn serialize<S: Serializer>(variant: &Box<dyn Variant>, serializer: S) -> Result<S::Ok, S::Error> {
+        if by_name(variant.name()).is_none() {
+            return Err(<S::Error as ser::Error>::custom(format!(
+                "variant \"{}\" can't be saved, only built-in variants can",
+                variant.name()
+            )));
+        }
+        serializer.serialize_str(variant.name())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Box<dyn Variant>, D::Error> {
+        use de::Error;
+        let name = String::deserialize(deserializer)?;
+        by_name(&name).ok_or_else(|| D::Error::custom(format!("unknown variant \"{}\"", name)))
+    }
+}
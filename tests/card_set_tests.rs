@@ -0,0 +1,112 @@
+use joker_poker::{Card, CardSet, Hand, Rank, Suit};
+
+fn set(notation: &str) -> CardSet {
+    CardSet::from(&notation.parse::<Hand>().unwrap())
+}
+
+mod index_tests {
+    use super::*;
+
+    #[test]
+    fn test_every_card_round_trips() {
+        for index in 0..64 {
+            let card = Card::from_index(index).unwrap();
+            assert_eq!(card.index(), index);
+        }
+    }
+
+    #[test]
+    fn test_index_layout() {
+        assert_eq!(Card::new(Rank::Two, Suit::Hearts).index(), 0);
+        assert_eq!(Card::new(Rank::Two, Suit::Spades).index(), 3);
+        assert_eq!(Card::new(Rank::Ace, Suit::Spades).index(), 51);
+        assert_eq!(Card::joker(0).index(), 52);
+        assert_eq!(Card::joker(3).index(), 55);
+    }
+}
+
+mod set_operation_tests {
+    use super::*;
+
+    #[test]
+    fn test_insert_remove_contains() {
+        let mut cards = CardSet::new();
+        assert!(cards.is_empty());
+        assert!(cards.insert(Card::new(Rank::Ace, Suit::Hearts)));
+        assert!(!cards.insert(Card::new(Rank::Ace, Suit::Hearts)));
+        assert!(cards.insert(Card::joker(1)));
+        assert_eq!(cards.len(), 2);
+        assert!(cards.contains(Card::joker(1)));
+        assert!(!cards.contains(Card::joker(0)));
+        assert!(cards.remove(Card::joker(1)));
+        assert!(!cards.remove(Card::joker(1)));
+        assert_eq!(cards, CardSet::from(Card::new(Rank::Ace, Suit::Hearts)));
+
+        // Jokers past the twelve a set can hold are simply never in it
+        assert!(!cards.contains(Card::joker(20)));
+        assert!(!cards.remove(Card::joker(20)));
+    }
+
+    #[test]
+    fn test_union_intersection_difference() {
+        let a = set("Ah Kd Jk");
+        let b = set("Kd 2c");
+        assert_eq!(a.union(b), set("Ah Kd 2c Jk"));
+        assert_eq!(a | b, a.union(b));
+        assert_eq!(a.intersection(b), set("Kd"));
+        assert_eq!(a & b, a.intersection(b));
+        assert_eq!(a.difference(b), set("Ah Jk"));
+        assert_eq!(a - b, a.difference(b));
+    }
+
+    #[test]
+    fn test_full_set() {
+        assert_eq!(CardSet::full(0).len(), 52);
+        let full = CardSet::full(2);
+        assert_eq!(full.len(), 54);
+        assert!(full.contains(Card::joker(1)));
+        assert!(!full.contains(Card::joker(2)));
+        assert_eq!(CardSet::full(12).bits(), u64::MAX);
+    }
+}
+
+mod conversion_tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_iterates_in_index_order() {
+        let cards: Vec<Card> = set("Jk Ks 2c Kh").iter().collect();
+        assert_eq!(cards, "2c Kh Ks Jk".parse::<Hand>().unwrap().cards);
+        assert_eq!(set("Jk Ks 2c").iter().len(), 3);
+    }
+
+    #[test]
+    fn test_hand_conversions() {
+        let cards = set("Td 9s Jk Jk");
+        assert_eq!(cards.len(), 4);
+        assert_eq!(Hand::from(cards).to_string(), "9s Td Jk Jk1");
+        assert_eq!(cards.to_string(), "9s Td Jk Jk1");
+        let collected: CardSet = Hand::from(cards).cards.into_iter().collect();
+        assert_eq!(collected, cards);
+    }
+
+    #[test]
+    fn test_sample_draws_distinct_members() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let deck = CardSet::full(2);
+        let sample = deck.sample(&mut rng, 5);
+        assert_eq!(sample.len(), 5);
+        assert_eq!(sample.difference(deck), CardSet::new());
+        assert_eq!(set("Ah Kd").sample(&mut rng, 5), set("Ah Kd"));
+    }
+
+    #[test]
+    fn test_sample_is_repeatable_with_a_seed() {
+        let deck = CardSet::full(0);
+        let first = deck.sample(&mut StdRng::seed_from_u64(3), 7);
+        let second = deck.sample(&mut StdRng::seed_from_u64(3), 7);
+        assert_eq!(first, second);
+    }
+}
@@ -0,0 +1,43 @@
+// Helpers shared by the integration tests. Every test file builds its own
+// copy of this module, so each one leaves some of them unused.
+#![allow(dead_code)]
+
+use joker_poker::game::GameState;
+use joker_poker::{Action, Deck, Game, GameEvent, Hand, Variant};
+
+// Builds a hand from card notation, e.g. "Ah Kd Tc 9s Jk"
+pub fn hand(notation: &str) -> Hand {
+    notation.parse().expect("valid hand notation")
+}
+
+// Helper function to create a new game with default settings
+pub fn create_test_game(num_players: u32, starting_chips: u32, min_bet: u32, num_jokers: u32) -> Game {
+    let player_names: Vec<String> = (1..=num_players)
+        .map(|i| format!("Player{}", i))
+        .collect();
+    Game::new(player_names, starting_chips, min_bet, num_jokers).unwrap()
+}
+
+// A game of `variant` without jokers whose deck deals `top` first, in the
+// order `Deck::stacked` describes
+pub fn stacked_game(variant: impl Variant + 'static, num_players: u32, top: &str) -> Game {
+    let mut game = create_test_game(num_players, 1000, 10, 0);
+    game.set_variant(Box::new(variant)).unwrap();
+    game.set_stacked_deck(Deck::stacked(hand(top).cards, 0).unwrap());
+    game
+}
+
+// Checks or calls until the round is over, drawing no cards, and collects
+// the events
+pub fn play_passively(game: &mut Game) -> Vec<GameEvent> {
+    let mut events = Vec::new();
+    while !game.is_game_over() {
+        let action = match game.get_state() {
+            GameState::Drawing => Action::Draw(Vec::new()),
+            _ if game.get_amount_to_call(game.get_current_player()) == 0 => Action::Check,
+            _ => Action::Call,
+        };
+        events.extend(game.apply(game.get_current_player(), action).unwrap());
+    }
+    events
+}
@@ -0,0 +1,247 @@
+use joker_poker::{Deck, GameError};
+
+mod deck_creation_tests {
+    use super::*;
+
+    #[test]
+    fn test_deck_sizes() {
+        assert_eq!(Deck::new(0).len(), 52);
+        assert_eq!(Deck::new(2).len(), 54);
+        assert_eq!(Deck::new(4).len(), 56);
+    }
+
+    #[test]
+    fn test_joker_count() {
+        let mut deck = Deck::new(3);
+        let hand = deck.deal(55).unwrap();
+        assert_eq!(hand.cards.iter().filter(|c| c.is_joker()).count(), 3);
+    }
+}
+
+mod dealing_tests {
+    use super::*;
+
+    #[test]
+    fn test_deal_takes_from_stub() {
+        let mut deck = Deck::new(2);
+        let hand = deck.deal(5).unwrap();
+        assert_eq!(hand.cards.len(), 5);
+        assert_eq!(deck.len(), 49);
+    }
+
+    #[test]
+    fn test_deal_whole_deck() {
+        let mut deck = Deck::new(0);
+        deck.deal(52).unwrap();
+        assert!(deck.is_empty());
+        assert!(matches!(deck.deal(1), Err(GameError::EmptyDeck)));
+    }
+
+    #[test]
+    fn test_short_stub_deals_nothing() {
+        let mut deck = Deck::new(0);
+        deck.deal(50).unwrap();
+
+        let result = deck.deal(3);
+        assert!(matches!(result, Err(GameError::EmptyDeck)));
+        assert!(result.unwrap_err().is_fatal());
+        assert_eq!(deck.len(), 2);
+    }
+}
+
+mod discard_pile_tests {
+    use super::*;
+
+    #[test]
+    fn test_discards_are_kept_out_of_the_stub() {
+        let mut deck = Deck::new(0);
+        let hand = deck.deal(5).unwrap();
+        deck.discard(hand.cards);
+
+        assert_eq!(deck.len(), 47);
+        assert_eq!(deck.discards_len(), 5);
+    }
+
+    #[test]
+    fn test_reshuffle_goes_under_the_stub() {
+        let mut deck = Deck::new(0);
+        let first = deck.deal(50).unwrap();
+        deck.discard(first.cards[..10].to_vec());
+
+        deck.reshuffle_discards();
+        assert_eq!(deck.len(), 12);
+        assert_eq!(deck.discards_len(), 0);
+
+        // The two stub cards come out before any reshuffled discard
+        let next = deck.deal(2).unwrap();
+        assert!(next.cards.iter().all(|card| !first.cards.iter().any(|c| c == card)));
+        assert_eq!(deck.deal(10).unwrap().cards.len(), 10);
+    }
+}
+
+mod remaining_cards_tests {
+    use super::*;
+    use joker_poker::CardSet;
+
+    #[test]
+    fn test_remaining_tracks_the_stub() {
+        let mut deck = Deck::new(2);
+        assert_eq!(deck.remaining(), CardSet::full(2));
+
+        let hand = deck.deal(5).unwrap();
+        let dealt = CardSet::from(&hand);
+        assert_eq!(deck.remaining(), CardSet::full(2) - dealt);
+        assert!(hand.cards.iter().all(|&card| !deck.contains(card)));
+
+        deck.discard(hand.cards);
+        assert_eq!(deck.remaining().len(), 49);
+        deck.reshuffle_discards();
+        assert_eq!(deck.remaining(), CardSet::full(2));
+        assert!(!deck.contains(joker_poker::Card::joker(20)));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_too_many_jokers() {
+        Deck::new(13);
+    }
+}
+
+mod seeded_shuffle_tests {
+    use super::*;
+    use joker_poker::Card;
+
+    fn order(deck: &mut Deck) -> Vec<(String, bool)> {
+        let len = deck.len();
+        deck.deal(len).unwrap().cards.iter()
+            .map(|card: &Card| (format!("{:?}", card), card.is_joker()))
+            .collect()
+    }
+
+    #[test]
+    fn test_same_seed_same_order() {
+        let mut first = Deck::with_seed(2, 42);
+        let mut second = Deck::with_seed(2, 42);
+        first.shuffle();
+        second.shuffle();
+        assert_eq!(order(&mut first), order(&mut second));
+    }
+
+    #[test]
+    fn test_different_seeds_differ() {
+        let mut first = Deck::with_seed(2, 1);
+        let mut second = Deck::with_seed(2, 2);
+        first.shuffle();
+        second.shuffle();
+        assert_ne!(order(&mut first), order(&mut second));
+    }
+
+    #[test]
+    fn test_injected_rng() {
+        use joker_poker::deck::DeckRng;
+        use rand::SeedableRng;
+
+        let mut injected = Deck::with_rng(0, DeckRng::seed_from_u64(9));
+        let mut seeded = Deck::with_seed(0, 9);
+        injected.shuffle();
+        seeded.shuffle();
+        assert_eq!(order(&mut injected), order(&mut seeded));
+    }
+
+    #[test]
+    fn test_shuffles_like_std_rng() {
+        use rand::rngs::StdRng;
+        use rand::seq::SliceRandom;
+        use rand::SeedableRng;
+
+        let mut deck = Deck::with_seed(0, 9);
+        let mut cards = deck.deal(52).unwrap().cards;
+        let mut fresh = Deck::with_seed(0, 9);
+        fresh.shuffle();
+        cards.shuffle(&mut StdRng::seed_from_u64(9));
+        assert_eq!(fresh.deal(52).unwrap().cards, cards);
+    }
+}
+
+mod preset_deck_tests {
+    use super::*;
+    use joker_poker::{Card, Rank, Suit};
+
+    fn full_order(num_jokers: u32) -> Vec<Card> {
+        let mut cards = Vec::new();
+        for rank in Rank::ALL.iter().rev() {
+            for suit in Suit::ALL {
+                cards.push(Card::new(*rank, suit));
+            }
+        }
+        cards.extend((0..num_jokers).map(|id| Card::joker(id as u8)));
+        cards
+    }
+
+    #[test]
+    fn test_from_cards_keeps_order() {
+        let mut deck = Deck::from_cards(full_order(2), 2).unwrap();
+        let first = deck.deal(5).unwrap();
+        assert_eq!(first.cards[0], Card::new(Rank::Ace, Suit::Hearts));
+        assert_eq!(first.cards[4].rank(), Some(Rank::King));
+        assert_eq!(deck.len(), 49);
+    }
+
+    #[test]
+    fn test_from_cards_rejects_duplicates() {
+        let mut cards = full_order(0);
+        cards[51] = cards[0];
+        assert!(matches!(Deck::from_cards(cards, 0), Err(GameError::DuplicateCard(_))));
+    }
+
+    #[test]
+    fn test_from_cards_rejects_missing_cards() {
+        let mut cards = full_order(0);
+        cards.pop();
+        assert!(matches!(Deck::from_cards(cards, 0), Err(GameError::InvalidCardCount(51))));
+    }
+
+    #[test]
+    fn test_from_cards_checks_jokers() {
+        assert!(matches!(Deck::from_cards(full_order(1), 2), Err(GameError::InvalidJokerCount(1))));
+        assert!(Deck::from_cards(full_order(1), 1).is_ok());
+
+        let mut cards = full_order(2);
+        cards[53] = Card::joker(0);
+        assert!(matches!(Deck::from_cards(cards, 2), Err(GameError::DuplicateCard(_))));
+        let mut cards = full_order(2);
+        cards[53] = Card::joker(7);
+        assert!(matches!(Deck::from_cards(cards, 2), Err(GameError::InvalidJokerCount(2))));
+    }
+
+    #[test]
+    fn test_stacked_puts_cards_on_top() {
+        let top = vec![
+            Card::new(Rank::Seven, Suit::Clubs),
+            Card::joker(0),
+        ];
+        let mut deck = Deck::stacked(top, 2).unwrap();
+        assert_eq!(deck.len(), 54);
+        let first = deck.deal(3).unwrap();
+        assert_eq!(first.cards[0], Card::new(Rank::Seven, Suit::Clubs));
+        assert_eq!(first.cards[1], Card::joker(0));
+        assert!(!first.cards[2].is_joker());
+        let rest = deck.deal(51).unwrap();
+        assert_eq!(rest.cards.iter().filter(|c| c.is_joker()).collect::<Vec<_>>(), [&Card::joker(1)]);
+        assert!(!rest.cards.contains(&Card::new(Rank::Seven, Suit::Clubs)));
+    }
+
+    #[test]
+    fn test_stacked_rejects_bad_tops() {
+        let twice = vec![Card::new(Rank::Two, Suit::Spades); 2];
+        assert!(matches!(Deck::stacked(twice, 0), Err(GameError::DuplicateCard(_))));
+
+        let jokers = vec![Card::joker(0), Card::joker(1), Card::joker(2)];
+        assert!(matches!(Deck::stacked(jokers, 2), Err(GameError::InvalidJokerCount(3))));
+
+        let same_joker = vec![Card::joker(1); 2];
+        assert!(matches!(Deck::stacked(same_joker, 2), Err(GameError::DuplicateCard(_))));
+
+        assert!(matches!(Deck::stacked(Vec::new(), 13), Err(GameError::InvalidJokerCount(13))));
+    }
+}
@@ -0,0 +1,141 @@
+use joker_poker::{Card, Hand, HandRank, Rank, Suit};
+
+// Brute-force reference evaluator. Every joker is tried as every one of the
+// 52 cards (a joker may duplicate a card already held) and the best category
+// wins. Deliberately naive so that it shares nothing with Hand::evaluate.
+mod reference {
+    use super::*;
+
+    pub fn evaluate(cards: &[Card]) -> HandRank {
+        let naturals: Vec<(u8, u8)> = cards.iter()
+            .filter_map(Card::natural)
+            .map(|(rank, suit)| (rank.to_u8(), suit_index(suit)))
+            .collect();
+        let jokers = cards.len() - naturals.len();
+        let mut hand = [(0u8, 0u8); 5];
+        hand[..naturals.len()].copy_from_slice(&naturals);
+        best_with_substitutes(&mut hand, naturals.len(), jokers, 0)
+    }
+
+    fn suit_index(suit: Suit) -> u8 {
+        Suit::ALL.iter().position(|&s| s == suit).unwrap() as u8
+    }
+
+    // Fills slots `filled..` with every multiset of cards starting at `first`
+    fn best_with_substitutes(hand: &mut [(u8, u8); 5], filled: usize, jokers: usize, first: u8) -> HandRank {
+        if jokers == 0 {
+            return evaluate_concrete(hand);
+        }
+        let mut best = HandRank::HighCard;
+        for code in first..52 {
+            hand[filled] = (code / 4, code % 4);
+            best = best.max(best_with_substitutes(hand, filled + 1, jokers - 1, code));
+            if best == HandRank::FiveOfAKind {
+                break;
+            }
+        }
+        best
+    }
+
+    pub fn evaluate_concrete(hand: &[(u8, u8); 5]) -> HandRank {
+        let mut counts = [0u8; 13];
+        let mut mask = 0u16;
+        for &(rank, _) in hand {
+            counts[rank as usize] += 1;
+            mask |= 1 << rank;
+        }
+        let distinct = mask.count_ones();
+        let most = *counts.iter().max().unwrap();
+
+        let flush = hand.iter().all(|&(_, suit)| suit == hand[0].1);
+        let low = mask.trailing_zeros();
+        let wheel = mask == 0b1_0000_0000_1111;
+        let straight = distinct == 5 && (mask >> low == 0b1_1111 || wheel);
+
+        match (distinct, most) {
+            (1, _) => HandRank::FiveOfAKind,
+            _ if straight && flush && low == 8 => HandRank::RoyalFlush,
+            _ if straight && flush => HandRank::StraightFlush,
+            (2, 4) => HandRank::FourOfAKind,
+            (2, 3) => HandRank::FullHouse,
+            _ if flush => HandRank::Flush,
+            _ if straight => HandRank::Straight,
+            (3, 3) => HandRank::ThreeOfAKind,
+            (3, 2) => HandRank::TwoPair,
+            (4, 2) => HandRank::OnePair,
+            _ => HandRank::HighCard,
+        }
+    }
+}
+
+fn joker(id: u8) -> Card {
+    Card::joker(id)
+}
+
+// The 52 natural cards followed by two jokers
+fn full_deck() -> Vec<Card> {
+    let mut cards = Vec::new();
+    for suit in Suit::ALL {
+        for rank in Rank::ALL {
+            cards.push(Card::new(rank, suit));
+        }
+    }
+    cards.push(joker(0));
+    cards.push(joker(1));
+    cards
+}
+
+#[test]
+fn test_reference_spot_checks() {
+    let hand = |cards: &[(Rank, Suit)], jokers: usize| -> Vec<Card> {
+        let mut hand: Vec<Card> = cards.iter().map(|&(r, s)| Card::new(r, s)).collect();
+        hand.extend((0..jokers).map(|id| joker(id as u8)));
+        hand
+    };
+    assert_eq!(
+        reference::evaluate(&hand(&[(Rank::King, Suit::Hearts), (Rank::Ace, Suit::Clubs), (Rank::Two, Suit::Spades), (Rank::Three, Suit::Hearts), (Rank::Four, Suit::Diamonds)], 0)),
+        HandRank::HighCard
+    );
+    assert_eq!(
+        reference::evaluate(&hand(&[(Rank::Ten, Suit::Hearts), (Rank::Jack, Suit::Hearts), (Rank::Queen, Suit::Hearts)], 2)),
+        HandRank::RoyalFlush
+    );
+    assert_eq!(
+        reference::evaluate(&hand(&[(Rank::Two, Suit::Hearts), (Rank::Two, Suit::Clubs), (Rank::Nine, Suit::Hearts), (Rank::Nine, Suit::Spades)], 1)),
+        HandRank::FullHouse
+    );
+}
+
+#[test]
+fn test_every_five_card_hand_from_54_cards() {
+    let deck = full_deck();
+    let mut mismatches = Vec::new();
+    let mut checked = 0;
+
+    for a in 0..deck.len() {
+        for b in a + 1..deck.len() {
+            for c in b + 1..deck.len() {
+                for d in c + 1..deck.len() {
+                    for e in d + 1..deck.len() {
+                        let cards = vec![deck[a], deck[b], deck[c], deck[d], deck[e]];
+                        let expected = reference::evaluate(&cards);
+                        let hand = Hand::new(cards);
+                        let (evaluated, valued) = (hand.evaluate(), hand.value().category);
+                        if evaluated != expected || valued != expected {
+                            mismatches.push((hand.cards, expected, evaluated, valued));
+                        }
+                        checked += 1;
+                    }
+                }
+            }
+        }
+    }
+
+    assert_eq!(checked, 3_162_510);
+    assert!(
+        mismatches.is_empty(),
+        "{} mismatches (cards, reference, evaluate, value), first few: {:?}",
+        mismatches.len(),
+        &mismatches[..mismatches.len().min(5)]
+    );
+}
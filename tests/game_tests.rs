@@ -1,24 +1,10 @@
-use joker_poker::{Game, Player, HandRank, Card, Rank, Suit, Hand};
+use joker_poker::{Action, Deck, Game, Rank, Hand};
+use joker_poker::{EventSink, GameEvent, PlayerController, TableView};
+use joker_poker::error::GameError;
 use joker_poker::game::GameState;
-use std::io::{self, Write};
-use std::thread;
-use std::time::Duration;
-
-// Helper function to create a new game with default settings
-fn create_test_game(num_players: u32, starting_chips: u32, min_bet: u32, num_jokers: u32) -> Game {
-    let player_names: Vec<String> = (1..=num_players)
-        .map(|i| format!("Player{}", i))
-        .collect();
-    Game::new(player_names, starting_chips, min_bet, num_jokers)
-}
 
-// Helper function to create a specific hand for testing
-fn create_test_hand(cards: Vec<(Rank, Suit, bool)>) -> Hand {
-    let cards = cards.into_iter()
-        .map(|(rank, suit, is_joker)| Card::new(rank, suit, is_joker))
-        .collect();
-    Hand::new(cards)
-}
+mod common;
+use common::*;
 
 mod game_initialization_tests {
     use super::*;
@@ -58,6 +44,20 @@ mod game_initialization_tests {
         assert!(game_no_jokers.get_min_bet() == 10);
         assert!(game_max_jokers.get_min_bet() == 10);
     }
+
+    #[test]
+    fn test_set_num_jokers_rejects_too_many() {
+        let mut game = create_test_game(2, 1000, 10, 0);
+        assert!(game.set_num_jokers(12).is_ok());
+        assert!(matches!(game.set_num_jokers(13), Err(GameError::InvalidJokerCount(13))));
+    }
+
+    #[test]
+    fn test_new_game_rejects_too_many_jokers() {
+        let names = vec!["Alice".to_string(), "Bob".to_string()];
+        assert!(Game::new(names.clone(), 1000, 10, 12).is_ok());
+        assert!(matches!(Game::new(names, 1000, 10, 13), Err(GameError::InvalidJokerCount(13))));
+    }
 }
 
 
@@ -153,13 +153,7 @@ mod drawing_round_tests {
         let mut game = create_test_game(2, 1000, 10, 2);
         
         // Set a known hand for player 1
-        let player1_hand = create_test_hand(vec![
-            (Rank::Ace, Suit::Hearts, false),
-            (Rank::King, Suit::Hearts, false),
-            (Rank::Queen, Suit::Hearts, false),
-            (Rank::Jack, Suit::Hearts, false),
-            (Rank::Ten, Suit::Hearts, false),
-        ]);
+        let player1_hand = hand("Ah Kh Qh Jh Th");
         
         // Just directly set player 1's hand
         game.set_player_hand(0, player1_hand);
@@ -173,12 +167,197 @@ mod drawing_round_tests {
         }
     }
 
+    fn no_ace_hand() -> Hand {
+        hand("Kh 9c 7d 4s 2h")
+    }
+
+    fn drawing_game() -> Game {
+        let mut game = create_test_game(2, 1000, 10, 2);
+        game.setup_test_round();
+        game.set_player_hand(0, no_ace_hand());
+        game.set_game_state(GameState::Drawing);
+        game
+    }
+
+    // Ten players and no jokers leave a two-card stub. Player 10 folds, so
+    // the discard pile holds their five cards.
+    fn short_stub_game() -> Game {
+        let mut game = create_test_game(10, 1000, 10, 0);
+        game.setup_test_round();
+        game.test_fold_player(9);
+        game.set_player_hand(0, no_ace_hand());
+        game.set_game_state(GameState::Drawing);
+        game
+    }
+
+    fn cards_in_play(game: &Game) -> usize {
+        let held: usize = game.get_players().iter()
+            .filter_map(|p| p.hand.as_ref())
+            .map(|hand| hand.cards.len())
+            .sum();
+        held + game.get_deck().len() + game.get_deck().discards_len()
+    }
+
+    #[test]
+    fn test_draw_from_short_stub_fails() {
+        let mut game = short_stub_game();
+        assert_eq!(game.get_deck().len(), 2);
+        assert!(game.get_players()[9].hand.is_none());
+
+        let result = game.draw(0, &[0, 1, 2]);
+        assert!(matches!(result, Err(GameError::EmptyDeck)));
+        assert_eq!(game.get_players()[0].hand.as_ref().unwrap().cards[0].rank(), Some(Rank::King));
+        assert_eq!(game.get_deck().len(), 2);
+    }
+
+    #[test]
+    fn test_draw_reshuffles_discard_pile() {
+        let mut game = short_stub_game();
+        game.set_reshuffle_discards(true);
+
+        game.draw(0, &[0, 1, 2]).expect("The discard pile should cover the draw");
+        assert_eq!(game.get_players()[0].hand.as_ref().unwrap().cards.len(), 5);
+        assert_eq!(game.get_deck().len(), 4);
+        assert_eq!(game.get_deck().discards_len(), 3);
+        assert_eq!(cards_in_play(&game), 52);
+    }
+
+    #[test]
+    fn test_own_discards_are_not_redealt() {
+        let mut game = create_test_game(10, 1000, 10, 0);
+        game.setup_test_round();
+        game.set_game_state(GameState::Drawing);
+        game.set_reshuffle_discards(true);
+
+        // Player 1 empties the stub; only their two cards are on the pile
+        game.draw(0, &[0, 1]).unwrap();
+        assert_eq!(game.get_deck().len(), 0);
+
+        // Player 2's own three discards can't make up the shortfall
+        let result = game.draw(1, &[0, 1, 2]);
+        assert!(matches!(result, Err(GameError::EmptyDeck)));
+        assert_eq!(game.get_deck().len(), 2);
+        assert_eq!(game.get_deck().discards_len(), 0);
+        assert_eq!(cards_in_play(&game), 52);
+
+        game.draw(1, &[0, 1]).expect("Two reshuffled cards cover a two-card draw");
+        assert_eq!(cards_in_play(&game), 52);
+    }
+
+    #[test]
+    fn test_legal_draws_fit_the_stub() {
+        let mut game = short_stub_game();
+        let actions = game.legal_actions();
+        assert!(actions.contains(&Action::Draw(vec![0, 1])));
+        assert!(actions.iter().all(|action| matches!(action, Action::Draw(d) if d.len() <= 2)));
+
+        // The folded hand on the discard pile covers a bigger draw
+        game.set_reshuffle_discards(true);
+        assert!(game.legal_actions().contains(&Action::Draw(vec![0, 1, 2])));
+    }
+
+    #[test]
+    fn test_draw_replaces_discarded_cards() {
+        let mut game = drawing_game();
+        let deck_before = game.get_deck().len();
+
+        game.draw(0, &[0, 2]).expect("Drawing two cards should succeed");
+
+        let hand = game.get_players()[0].hand.as_ref().unwrap();
+        assert_eq!(hand.cards.len(), 5);
+        // Kept cards stay in order, replacements follow
+        assert_eq!(hand.cards[0].rank(), Some(Rank::Nine));
+        assert_eq!(hand.cards[1].rank(), Some(Rank::Four));
+        assert_eq!(hand.cards[2].rank(), Some(Rank::Two));
+        assert_eq!(game.get_deck().len(), deck_before - 2);
+    }
+
+    #[test]
+    fn test_stand_pat() {
+        let mut game = drawing_game();
+        let deck_before = game.get_deck().len();
+
+        game.draw(0, &[]).expect("Standing pat should succeed");
+
+        assert_eq!(game.get_players()[0].hand.as_ref().unwrap().cards[0].rank(), Some(Rank::King));
+        assert_eq!(game.get_deck().len(), deck_before);
+    }
+
     #[test]
     fn test_max_draw_limit() {
-        // Since we don't have a way to test drawing directly yet,
-        // this is more of a placeholder
-        let mut game = create_test_game(2, 1000, 10, 2);
-        assert!(true); // Placeholder assertion
+        let mut game = drawing_game();
+
+        let result = game.draw(0, &[0, 1, 2, 3]);
+        assert!(matches!(result, Err(GameError::InvalidDraw(_))));
+        // A rejected draw leaves the hand alone
+        assert_eq!(game.get_players()[0].hand.as_ref().unwrap().cards[0].rank(), Some(Rank::King));
+
+        game.draw(0, &[0, 1, 2]).expect("Drawing three cards should succeed");
+    }
+
+    #[test]
+    fn test_four_card_draw_keeping_ace() {
+        let mut game = drawing_game();
+        game.set_player_hand(0, hand("Ah 9c 7d 4s 2h"));
+
+        // Discarding the Ace doesn't count as holding one
+        let result = game.draw(0, &[0, 1, 2, 3]);
+        assert!(matches!(result, Err(GameError::InvalidDraw(_))));
+
+        game.draw(0, &[1, 2, 3, 4]).expect("Four cards may be drawn when keeping an Ace");
+        assert_eq!(game.get_players()[0].hand.as_ref().unwrap().cards[0].rank(), Some(Rank::Ace));
+    }
+
+    #[test]
+    fn test_joker_is_not_an_ace_for_draw_limit() {
+        let mut game = drawing_game();
+        game.set_player_hand(0, hand("Jk 9c 7d 4s 2h"));
+
+        let result = game.draw(0, &[1, 2, 3, 4]);
+        assert!(matches!(result, Err(GameError::InvalidDraw(_))));
+    }
+
+    #[test]
+    fn test_invalid_draw_positions() {
+        let mut game = drawing_game();
+
+        assert!(matches!(game.draw(0, &[5]), Err(GameError::InvalidDraw(_))));
+        assert!(matches!(game.draw(0, &[1, 1]), Err(GameError::InvalidDraw(_))));
+    }
+
+    #[test]
+    fn test_draw_outside_drawing_state() {
+        let mut game = drawing_game();
+        game.set_game_state(GameState::Betting);
+
+        let result = game.draw(0, &[0]);
+        assert!(matches!(result, Err(GameError::InvalidGameState { .. })));
+    }
+
+    #[test]
+    fn test_draw_out_of_turn() {
+        let mut game = drawing_game();
+        assert_eq!(game.get_current_player(), 0);
+        assert!(matches!(game.draw(1, &[0]), Err(GameError::InvalidAction(_))));
+        assert_eq!(game.get_players()[1].hand.as_ref().unwrap().cards.len(), 5);
+    }
+
+    #[test]
+    fn test_each_player_draws_once() {
+        let mut game = drawing_game();
+        game.draw(0, &[0, 1, 2]).unwrap();
+        assert_eq!(game.get_current_player(), 1);
+        // A second draw would get round the three-card limit
+        assert!(matches!(game.draw(0, &[0, 1, 2]), Err(GameError::InvalidAction(_))));
+    }
+
+    #[test]
+    fn test_folded_player_cannot_draw() {
+        let mut game = drawing_game();
+        game.test_fold_player(1);
+
+        let result = game.draw(1, &[0]);
+        assert!(matches!(result, Err(GameError::PlayerNotInRound(_))));
     }
 }
 
@@ -187,7 +366,7 @@ mod game_state_tests {
 
     #[test]
     fn test_game_progression() {
-        let mut game = create_test_game(2, 1000, 10, 2);
+        let game = create_test_game(2, 1000, 10, 2);
         assert!(!game.is_game_over());
     }
 
@@ -218,17 +397,14 @@ mod error_handling_tests {
         let result = game.test_place_bet(0, 5);
         assert!(result.is_err(), "Betting below minimum should fail");
         
-        // Verify the error message is related to minimum bet
-        if let Err(err) = result {
-            assert!(err.contains("below minimum"), 
-                    "Error should mention bet being below minimum: {}", err);
-        } else {
-            panic!("Expected an error for betting below minimum");
-        }
+        // Verify the error reports the allowed range
+        assert!(matches!(result, Err(GameError::BetOutOfRange { min: 10, max: 1000, attempted: 5 })),
+                "Error should report the bet as below minimum: {:?}", result);
         
         // Try to bet more than player has
         let result = game.test_place_bet(0, 2000);
-        assert!(result.is_err(), "Betting more than player has should fail");
+        assert!(matches!(result, Err(GameError::InsufficientChips { needed: 2000, available: 1000 })),
+                "Betting more than player has should fail: {:?}", result);
         
         // Check that player's chips haven't changed after failed bets
         let players = game.get_players();
@@ -249,13 +425,9 @@ mod error_handling_tests {
         let result = game.test_place_bet(0, 10); // Min bet is 50
         assert!(result.is_err(), "Bet below minimum should fail");
         
-        // Verify the error message
-        if let Err(err) = result {
-            assert!(err.contains("below minimum"), 
-                    "Error should mention bet being below minimum: {}", err);
-        } else {
-            panic!("Expected an error for betting below minimum");
-        }
+        // Verify the error reports the allowed range
+        assert!(matches!(result, Err(GameError::BetOutOfRange { min: 50, .. })),
+                "Error should report the bet as below minimum: {:?}", result);
         
         // Try valid minimum bet
         let result = game.test_place_bet(0, 50);
@@ -278,7 +450,8 @@ mod error_handling_tests {
         
         // Player can't meet minimum bet
         let result = game.test_place_bet(0, 100);
-        assert!(result.is_err(), "Player shouldn't be able to bet more than they have");
+        assert!(matches!(result, Err(GameError::InsufficientChips { needed: 100, available: 50 })),
+                "Player shouldn't be able to bet more than they have");
         
         // Check that the player is still in the round but with unchanged chips
         let players = game.get_players();
@@ -288,6 +461,19 @@ mod error_handling_tests {
         // Finish the round directly to avoid hanging
         game.set_game_state(GameState::GameOver);
     }
+
+    #[test]
+    fn test_bet_by_unknown_or_folded_player() {
+        let mut game = create_test_game(2, 1000, 10, 2);
+        game.setup_test_round();
+
+        assert!(matches!(game.test_place_bet(5, 10), Err(GameError::PlayerNotFound(_))));
+
+        game.test_fold_player(1);
+        let result = game.test_place_bet(1, 10);
+        assert!(matches!(result, Err(GameError::PlayerNotInRound(ref name)) if name == "Player2"));
+        assert!(!result.unwrap_err().can_retry());
+    }
 }
 
 mod game_mechanics_tests {
@@ -331,23 +517,11 @@ mod showdown_tests {
         game.setup_test_round();
         
         // Give player 1 a royal flush (best hand)
-        let royal_flush = create_test_hand(vec![
-            (Rank::Ace, Suit::Hearts, false),
-            (Rank::King, Suit::Hearts, false),
-            (Rank::Queen, Suit::Hearts, false),
-            (Rank::Jack, Suit::Hearts, false),
-            (Rank::Ten, Suit::Hearts, false),
-        ]);
+        let royal_flush = hand("Ah Kh Qh Jh Th");
         game.set_player_hand(0, royal_flush);
         
         // Give player 2 a straight flush (second-best hand)
-        let straight_flush = create_test_hand(vec![
-            (Rank::Nine, Suit::Clubs, false),
-            (Rank::Eight, Suit::Clubs, false),
-            (Rank::Seven, Suit::Clubs, false),
-            (Rank::Six, Suit::Clubs, false),
-            (Rank::Five, Suit::Clubs, false),
-        ]);
+        let straight_flush = hand("9c 8c 7c 6c 5c");
         game.set_player_hand(1, straight_flush);
         
         // Both players bet
@@ -407,21 +581,9 @@ mod showdown_tests {
         game.setup_test_round();
         
         // Give both players identical hands (for a tie)
-        let tied_hand1 = create_test_hand(vec![
-            (Rank::Ace, Suit::Hearts, false),
-            (Rank::Ace, Suit::Diamonds, false),
-            (Rank::King, Suit::Hearts, false),
-            (Rank::King, Suit::Diamonds, false),
-            (Rank::Queen, Suit::Hearts, false),
-        ]);
+        let tied_hand1 = hand("Ah Ad Kh Kd Qh");
         
-        let tied_hand2 = create_test_hand(vec![
-            (Rank::Ace, Suit::Clubs, false),
-            (Rank::Ace, Suit::Spades, false),
-            (Rank::King, Suit::Clubs, false),
-            (Rank::King, Suit::Spades, false),
-            (Rank::Queen, Suit::Clubs, false),
-        ]);
+        let tied_hand2 = hand("Ac As Kc Ks Qc");
         
         game.set_player_hand(0, tied_hand1);
         game.set_player_hand(1, tied_hand2);
@@ -442,7 +604,7 @@ mod showdown_tests {
         
         // Need to add the pot back for the second player
         // (this is a bit of a hack, but it works for testing)
-        let mut game_players = game.get_players_mut();
+        let game_players = game.get_players_mut();
         game_players[0].chips -= half_pot;
         game.set_pot(half_pot);
         game.distribute_pot_to_player(1);
@@ -525,4 +687,1185 @@ mod multi_round_tests {
         // Finish second round
         game.set_game_state(GameState::GameOver);
     }
-}
\ No newline at end of file
+}
+mod final_betting_tests {
+    use super::*;
+
+    #[test]
+    fn test_final_betting_state() {
+        let mut game = create_test_game(2, 1000, 10, 2);
+        game.setup_test_round();
+        game.set_game_state(GameState::FinalBetting);
+        assert_eq!(*game.get_state(), GameState::FinalBetting);
+        assert!(!game.is_game_over());
+    }
+
+    #[test]
+    fn test_no_drawing_after_final_betting_starts() {
+        let mut game = create_test_game(2, 1000, 10, 2);
+        game.setup_test_round();
+        game.set_game_state(GameState::FinalBetting);
+
+        let result = game.draw(0, &[0]);
+        assert!(matches!(
+            result,
+            Err(GameError::InvalidGameState { current: GameState::FinalBetting, expected: GameState::Drawing })
+        ));
+    }
+
+    #[test]
+    fn test_pot_carries_into_final_round() {
+        let mut game = create_test_game(2, 1000, 10, 2);
+        game.setup_test_round();
+        game.test_place_bet(0, 20).unwrap();
+        game.test_place_bet(1, 20).unwrap();
+
+        game.set_game_state(GameState::FinalBetting);
+        game.test_place_bet(0, 10).unwrap();
+
+        assert_eq!(game.get_pot(), 50);
+        assert_eq!(game.get_players()[0].chips, 970);
+    }
+}
+
+mod betting_accounting_tests {
+    use super::*;
+
+    #[test]
+    fn test_call_costs_only_the_difference() {
+        let mut game = create_test_game(3, 1000, 10, 0);
+        game.setup_test_round();
+
+        game.act(0, Action::Call).unwrap();
+        game.act(1, Action::Raise(30)).unwrap();
+        game.act(2, Action::Call).unwrap();
+        game.act(0, Action::Call).unwrap();
+
+        assert!(game.is_betting_complete());
+        assert_eq!(game.get_pot(), 90);
+        for player in game.get_players() {
+            assert_eq!(player.chips, 970);
+        }
+        assert_eq!(game.get_street_bet(0), 30);
+    }
+
+    #[test]
+    fn test_raise_reopens_action() {
+        let mut game = create_test_game(3, 1000, 10, 0);
+        game.setup_test_round();
+
+        game.act(0, Action::Call).unwrap();
+        game.act(1, Action::Call).unwrap();
+        game.act(2, Action::Raise(40)).unwrap();
+
+        assert!(!game.is_betting_complete());
+        assert_eq!(game.get_current_player(), 0);
+        assert_eq!(game.get_amount_to_call(0), 30);
+
+        game.act(0, Action::Call).unwrap();
+        assert!(!game.is_betting_complete());
+        game.act(1, Action::Fold).unwrap();
+        assert!(game.is_betting_complete());
+        assert_eq!(game.get_pot(), 90);
+    }
+
+    #[test]
+    fn test_check_facing_bet_is_rejected() {
+        let mut game = create_test_game(2, 1000, 10, 0);
+        game.setup_test_round();
+
+        assert!(matches!(game.act(0, Action::Check), Err(GameError::InvalidAction(_))));
+        assert_eq!(game.get_current_player(), 0);
+    }
+
+    #[test]
+    fn test_acting_out_of_turn_is_rejected() {
+        let mut game = create_test_game(2, 1000, 10, 0);
+        game.setup_test_round();
+
+        assert!(matches!(game.act(1, Action::Call), Err(GameError::InvalidAction(_))));
+        assert_eq!(game.get_pot(), 0);
+    }
+
+    #[test]
+    fn test_raise_below_minimum_is_rejected() {
+        let mut game = create_test_game(2, 1000, 10, 0);
+        game.setup_test_round();
+
+        let result = game.act(0, Action::Raise(15));
+        assert!(matches!(
+            result,
+            Err(GameError::BetOutOfRange { min: 20, max: 1000, attempted: 15 })
+        ));
+    }
+
+    #[test]
+    fn test_fold_leaves_one_player() {
+        let mut game = create_test_game(2, 1000, 10, 0);
+        game.setup_test_round();
+
+        game.act(0, Action::Fold).unwrap();
+        assert!(game.is_betting_complete());
+        assert!(matches!(game.act(0, Action::Call), Err(GameError::PlayerNotInRound(_))));
+    }
+
+    #[test]
+    fn test_players_without_chips_are_skipped() {
+        let mut game = create_test_game(3, 1000, 10, 0);
+        game.setup_test_round();
+        game.get_players_mut()[0].chips = 0;
+        game.start_betting_round();
+
+        assert_eq!(game.get_current_player(), 1);
+        game.act(1, Action::Call).unwrap();
+        game.act(2, Action::Call).unwrap();
+        assert!(game.is_betting_complete());
+    }
+
+    #[test]
+    fn test_final_round_opens_with_checks() {
+        let mut game = create_test_game(2, 1000, 10, 0);
+        game.setup_test_round();
+        game.set_game_state(GameState::FinalBetting);
+        game.start_betting_round();
+
+        assert_eq!(game.get_current_bet(), 0);
+        game.act(0, Action::Check).unwrap();
+        game.act(1, Action::Check).unwrap();
+        assert!(game.is_betting_complete());
+        assert_eq!(game.get_pot(), 0);
+    }
+}
+
+mod side_pot_tests {
+    use super::*;
+    use joker_poker::game::Pot;
+
+    fn royal_flush() -> Hand {
+        hand("As Ks Qs Js Ts")
+    }
+
+    fn pair_of_twos() -> Hand {
+        hand("2h 2c 5d 8s Jh")
+    }
+
+    fn high_card() -> Hand {
+        hand("3h 6c 9d Qd Kh")
+    }
+
+    // Player 0 has 50 chips, players 1 and 2 have 1000
+    fn short_stack_game() -> Game {
+        let mut game = create_test_game(3, 1000, 10, 0);
+        game.get_players_mut()[0].chips = 50;
+        game.setup_test_round();
+        game
+    }
+
+    // As above, with the deck stacked so the players are dealt `hands`
+    fn short_stack_game_dealt(hands: Vec<Hand>) -> Game {
+        let top = hands.into_iter().flat_map(|hand| hand.cards).collect();
+        let mut game = create_test_game(3, 1000, 10, 0);
+        game.set_stacked_deck(Deck::stacked(top, 0).unwrap());
+        game.get_players_mut()[0].chips = 50;
+        game.setup_test_round();
+        game
+    }
+
+    #[test]
+    fn test_call_for_less_goes_all_in() {
+        let mut game = create_test_game(2, 1000, 10, 0);
+        game.setup_test_round();
+        game.get_players_mut()[1].chips = 100;
+
+        game.act(0, Action::Raise(300)).unwrap();
+        game.act(1, Action::Call).unwrap();
+
+        assert!(game.get_players()[1].is_all_in());
+        assert_eq!(game.get_street_bet(1), 100);
+        assert_eq!(game.get_pot(), 400);
+        assert!(game.is_betting_complete());
+    }
+
+    #[test]
+    fn test_all_in_action() {
+        let mut game = short_stack_game();
+
+        game.act(0, Action::AllIn).unwrap();
+        assert_eq!(game.get_current_bet(), 50);
+        assert!(game.get_players()[0].is_all_in());
+
+        game.act(1, Action::Call).unwrap();
+        game.act(2, Action::Call).unwrap();
+        assert!(game.is_betting_complete());
+        assert_eq!(game.get_pot(), 150);
+    }
+
+    #[test]
+    fn test_short_all_in_does_not_reopen_raising() {
+        let mut game = create_test_game(3, 1000, 10, 0);
+        game.get_players_mut()[2].chips = 15;
+        game.setup_test_round();
+        game.act(0, Action::Call).unwrap();
+        game.act(1, Action::Call).unwrap();
+        // 15 is short of a full raise to 20
+        game.act(2, Action::AllIn).unwrap();
+        assert_eq!(game.get_current_bet(), 15);
+
+        assert_eq!(game.get_current_player(), 0);
+        assert_eq!(game.legal_actions(), vec![Action::Fold, Action::Call]);
+        assert_eq!(game.get_max_bet(0), 15);
+        assert!(matches!(game.act(0, Action::Raise(30)), Err(GameError::BetOutOfRange { .. })));
+        assert!(game.act(0, Action::AllIn).is_err());
+        game.act(0, Action::Call).unwrap();
+        game.act(1, Action::Call).unwrap();
+        assert!(game.is_betting_complete());
+        assert_eq!(game.get_pot(), 45);
+    }
+
+    #[test]
+    fn test_full_all_in_reopens_raising() {
+        let mut game = create_test_game(3, 1000, 10, 0);
+        game.get_players_mut()[2].chips = 40;
+        game.setup_test_round();
+        game.act(0, Action::Call).unwrap();
+        game.act(1, Action::Call).unwrap();
+        game.act(2, Action::AllIn).unwrap();
+
+        // The all-in raised by 30, so the next raise must too
+        assert!(game.legal_actions().contains(&Action::Raise(70)));
+        assert!(matches!(game.act(0, Action::Raise(60)), Err(GameError::BetOutOfRange { min: 70, .. })));
+        game.act(0, Action::Raise(70)).unwrap();
+        assert_eq!(game.get_current_player(), 1);
+    }
+
+    #[test]
+    fn test_all_in_short_of_the_last_raise_does_not_reopen_raising() {
+        let mut game = create_test_game(3, 1000, 10, 0);
+        game.get_players_mut()[1].chips = 120;
+        game.setup_test_round();
+        game.act(0, Action::Raise(100)).unwrap();
+        // 120 is more than the minimum bet above 100 but short of another 90
+        game.act(1, Action::AllIn).unwrap();
+        assert_eq!(game.get_current_bet(), 120);
+
+        assert_eq!(game.get_min_raise(), 210);
+        game.act(2, Action::Call).unwrap();
+        assert_eq!(game.get_current_player(), 0);
+        assert_eq!(game.legal_actions(), vec![Action::Fold, Action::Call]);
+        assert!(game.act(0, Action::Raise(130)).is_err());
+        game.act(0, Action::Call).unwrap();
+        assert!(game.is_betting_complete());
+    }
+
+    #[test]
+    fn test_all_in_player_is_skipped_in_later_rounds() {
+        let mut game = short_stack_game();
+        game.act(0, Action::AllIn).unwrap();
+        game.act(1, Action::Call).unwrap();
+        game.act(2, Action::Call).unwrap();
+
+        game.set_game_state(GameState::FinalBetting);
+        game.start_betting_round();
+        assert_eq!(game.get_current_player(), 1);
+        assert!(matches!(game.act(0, Action::Check), Err(GameError::InvalidAction(_))));
+    }
+
+    #[test]
+    fn test_no_betting_when_only_one_player_has_chips() {
+        let mut game = create_test_game(2, 1000, 10, 0);
+        game.get_players_mut()[0].chips = 50;
+        game.setup_test_round();
+        game.act(0, Action::AllIn).unwrap();
+        game.act(1, Action::Call).unwrap();
+
+        game.set_game_state(GameState::FinalBetting);
+        game.start_betting_round();
+        assert!(game.is_betting_complete());
+    }
+
+    #[test]
+    fn test_side_pot_calculation() {
+        let mut game = short_stack_game();
+        game.act(0, Action::AllIn).unwrap();
+        game.act(1, Action::Raise(200)).unwrap();
+        game.act(2, Action::Call).unwrap();
+
+        assert_eq!(game.side_pots(), vec![
+            Pot { amount: 150, eligible: vec![0, 1, 2] },
+            Pot { amount: 300, eligible: vec![1, 2] },
+        ]);
+    }
+
+    #[test]
+    fn test_folded_chips_stay_in_pots() {
+        let mut game = short_stack_game();
+        game.act(0, Action::AllIn).unwrap();
+        game.act(1, Action::Raise(200)).unwrap();
+        game.act(2, Action::Raise(400)).unwrap();
+        game.act(1, Action::Fold).unwrap();
+
+        // Player 2's unmatched chips can only go back to player 2
+        assert_eq!(game.side_pots(), vec![
+            Pot { amount: 150, eligible: vec![0, 2] },
+            Pot { amount: 500, eligible: vec![2] },
+        ]);
+    }
+
+    #[test]
+    fn test_short_stack_wins_only_main_pot() {
+        let mut game = short_stack_game_dealt(vec![royal_flush(), pair_of_twos(), high_card()]);
+        game.act(0, Action::AllIn).unwrap();
+        game.act(1, Action::Raise(200)).unwrap();
+        game.act(2, Action::Call).unwrap();
+
+        let payouts = game.award_pots();
+        assert_eq!(payouts, vec![vec![(0, 150)], vec![(1, 300)]]);
+        assert_eq!(game.get_players()[0].chips, 150);
+        assert_eq!(game.get_players()[1].chips, 1100);
+        assert_eq!(game.get_players()[2].chips, 800);
+        assert_eq!(game.get_pot(), 0);
+    }
+
+    #[test]
+    fn test_best_hand_takes_every_pot() {
+        let mut game = short_stack_game_dealt(vec![high_card(), royal_flush(), pair_of_twos()]);
+        game.act(0, Action::AllIn).unwrap();
+        game.act(1, Action::Raise(200)).unwrap();
+        game.act(2, Action::Call).unwrap();
+
+        game.award_pots();
+        assert_eq!(game.get_players()[1].chips, 1250);
+        assert_eq!(game.get_players()[1].get_wins(), 1);
+    }
+
+    #[test]
+    fn test_split_pot_odd_chip() {
+        let mut game = create_test_game(2, 1000, 10, 0);
+        game.setup_test_round();
+        game.set_player_hand(0, royal_flush());
+        game.set_player_hand(1, royal_flush());
+        game.set_pot(15);
+
+        let payouts = game.award_pots();
+        assert_eq!(payouts, vec![vec![(0, 8), (1, 7)]]);
+    }
+
+    #[test]
+    fn test_odd_chips_go_left_of_the_button() {
+        let mut game = create_test_game(3, 1000, 10, 0);
+        game.set_dealer(0).unwrap();
+        game.setup_test_round();
+        for player in 0..3 {
+            game.set_player_hand(player, royal_flush());
+        }
+        game.set_pot(17);
+
+        let payouts = game.award_pots();
+        assert_eq!(payouts, vec![vec![(0, 5), (1, 6), (2, 6)]]);
+
+        game.set_dealer(1).unwrap();
+        game.test_fold_player(0);
+        game.set_pot(15);
+        let payouts = game.award_pots();
+        assert_eq!(payouts, vec![vec![(1, 7), (2, 8)]]);
+    }
+}
+
+mod controller_tests {
+    use super::*;
+    use std::collections::VecDeque;
+
+    // Calls or checks every bet and stands pat
+    struct Passive;
+
+    impl PlayerController for Passive {
+        fn choose_action(&mut self, view: &TableView) -> Action {
+            if view.to_call == 0 { Action::Check } else { Action::Call }
+        }
+
+        fn choose_discards(&mut self, _view: &TableView) -> Vec<usize> {
+            Vec::new()
+        }
+    }
+
+    // Plays a fixed script, then falls back to passive play
+    struct Scripted {
+        actions: VecDeque<Action>,
+        discards: VecDeque<Vec<usize>>,
+    }
+
+    impl PlayerController for Scripted {
+        fn choose_action(&mut self, view: &TableView) -> Action {
+            self.actions.pop_front().unwrap_or_else(|| Passive.choose_action(view))
+        }
+
+        fn choose_discards(&mut self, _view: &TableView) -> Vec<usize> {
+            self.discards.pop_front().unwrap_or_default()
+        }
+    }
+
+    fn scripted(actions: Vec<Action>, discards: Vec<Vec<usize>>) -> Box<dyn PlayerController> {
+        Box::new(Scripted { actions: actions.into(), discards: discards.into() })
+    }
+
+    fn total_chips(game: &Game) -> u32 {
+        game.get_players().iter().map(|p| p.chips).sum::<u32>() + game.get_pot()
+    }
+
+    #[test]
+    fn test_round_runs_without_a_terminal() {
+        let mut game = create_test_game(3, 1000, 10, 2);
+        let mut controllers: Vec<Box<dyn PlayerController>> = vec![Box::new(Passive), Box::new(Passive), Box::new(Passive)];
+        let mut events: Vec<GameEvent> = Vec::new();
+
+        game.start_round(&mut controllers, &mut events).unwrap();
+
+        assert!(game.is_game_over());
+        assert_eq!(total_chips(&game), 3000);
+        assert!(matches!(events.first(), Some(GameEvent::RoundStarted { .. })));
+        assert!(matches!(events.last(), Some(GameEvent::RoundOver)));
+        let shown = events.iter().filter(|e| matches!(e, GameEvent::HandShown { .. })).count();
+        assert_eq!(shown, 3);
+        let awarded: u32 = events.iter()
+            .map(|e| if let GameEvent::PotAwarded { amount, .. } = e { *amount } else { 0 })
+            .sum();
+        assert_eq!(awarded, 30);
+    }
+
+    #[test]
+    fn test_controllers_must_match_players() {
+        let mut game = create_test_game(3, 1000, 10, 0);
+        let mut controllers: Vec<Box<dyn PlayerController>> = vec![Box::new(Passive)];
+        let mut events: Vec<GameEvent> = Vec::new();
+
+        assert!(matches!(
+            game.start_round(&mut controllers, &mut events),
+            Err(GameError::InvalidAction(_))
+        ));
+        assert!(events.is_empty());
+    }
+
+    #[test]
+    fn test_rejected_choices_are_reported_and_retried() {
+        let mut game = create_test_game(2, 1000, 10, 0);
+        let mut controllers = vec![
+            scripted(vec![Action::Check, Action::Raise(15)], vec![vec![0, 0]]),
+            scripted(vec![], vec![]),
+        ];
+        let mut events: Vec<GameEvent> = Vec::new();
+
+        game.start_round(&mut controllers, &mut events).unwrap();
+
+        let rejected: Vec<&GameEvent> = events.iter()
+            .filter(|e| matches!(e, GameEvent::ActionRejected { player: 0, .. }))
+            .collect();
+        assert_eq!(rejected.len(), 3);
+        assert!(events.iter().any(|e| matches!(e, GameEvent::Drew { player: 0, count: 0 })));
+        assert!(game.is_game_over());
+    }
+
+    #[test]
+    fn test_empty_deck_ends_the_round_with_an_error() {
+        // Ten players leave a two-card stub that can't cover three-card draws
+        let mut game = create_test_game(10, 1000, 10, 0);
+        let mut controllers: Vec<Box<dyn PlayerController>> = (0..10)
+            .map(|_| scripted(vec![], vec![vec![0, 1, 2]]))
+            .collect();
+        let mut events: Vec<GameEvent> = Vec::new();
+
+        let result = game.start_round(&mut controllers, &mut events);
+        assert!(matches!(result, Err(GameError::EmptyDeck)));
+        assert!(!events.iter().any(|e| matches!(e, GameEvent::ActionRejected { .. })));
+    }
+
+    #[test]
+    fn test_fold_skips_draw_and_awards_pot() {
+        let mut game = create_test_game(2, 1000, 10, 0);
+        let mut controllers = vec![
+            scripted(vec![Action::Raise(50)], vec![]),
+            scripted(vec![Action::Fold], vec![]),
+        ];
+        let mut events: Vec<GameEvent> = Vec::new();
+
+        game.start_round(&mut controllers, &mut events).unwrap();
+
+        assert!(!events.iter().any(|e| matches!(e, GameEvent::DrawingStarted)));
+        assert!(events.iter().any(|e| matches!(e, GameEvent::PotAwarded { pot: 0, player: 0, amount: 50 })));
+        assert_eq!(game.get_players()[0].chips, 1000);
+        assert_eq!(game.get_players()[1].chips, 1000);
+    }
+
+    #[test]
+    fn test_view_hides_other_hands() {
+        let mut game = create_test_game(2, 1000, 10, 0);
+        game.setup_test_round();
+        let view = game.table_view(1);
+
+        assert_eq!(view.player, 1);
+        assert_eq!(view.seats.len(), 2);
+        assert_eq!(view.seats[0].name, "Player1");
+        assert_eq!(view.to_call, 10);
+        assert_eq!(view.max_bet, 1000);
+        assert_eq!(view.hand.map(|h| h.cards.len()), Some(5));
+    }
+
+    #[test]
+    fn test_events_can_go_to_any_sink() {
+        struct Counter(usize);
+        impl EventSink for Counter {
+            fn emit(&mut self, _event: GameEvent) {
+                self.0 += 1;
+            }
+        }
+
+        let mut game = create_test_game(2, 1000, 10, 0);
+        let mut controllers: Vec<Box<dyn PlayerController>> = vec![Box::new(Passive), Box::new(Passive)];
+        let mut counter = Counter(0);
+        game.start_round(&mut controllers, &mut counter).unwrap();
+        assert!(counter.0 > 0);
+    }
+}
+
+mod state_machine_tests {
+    use super::*;
+
+    #[test]
+    fn test_begin_round_opens_betting() {
+        let mut game = create_test_game(3, 1000, 10, 0);
+        let events = game.begin_round().unwrap();
+
+        assert!(matches!(events[..], [GameEvent::RoundStarted { .. }, GameEvent::BettingStarted { final_round: false }]));
+        assert_eq!(*game.get_state(), GameState::Betting);
+        assert_eq!(game.get_current_player(), 0);
+        assert!(matches!(game.begin_round(), Err(GameError::RoundInProgress)));
+    }
+
+    #[test]
+    fn test_rounds_count_towards_player_stats() {
+        let mut game = create_test_game(3, 1000, 10, 0);
+        game.get_players_mut()[2].chips = 0;
+        game.begin_round().unwrap();
+        play_passively(&mut game);
+
+        let players = game.get_players();
+        assert_eq!(players[0].get_hands_played(), 1);
+        assert_eq!(players[1].get_hands_played(), 1);
+        assert_eq!(players[2].get_hands_played(), 0);
+        let winner = players.iter().find(|p| p.get_wins() > 0).expect("a winner");
+        assert!(winner.get_win_rate() > 0.0);
+    }
+
+    #[test]
+    fn test_uncontested_winner_shows_nothing() {
+        let mut game = create_test_game(3, 1000, 10, 0);
+        game.begin_round().unwrap();
+        game.apply(0, Action::Call).unwrap();
+        game.apply(1, Action::Fold).unwrap();
+        let events = game.apply(2, Action::Fold).unwrap();
+
+        assert!(game.is_game_over());
+        assert!(!events.iter().any(|e| matches!(e, GameEvent::HandShown { .. })));
+        assert!(events.iter().any(|e| matches!(e, GameEvent::PotAwarded { player: 0, .. })));
+    }
+
+    #[test]
+    fn test_apply_without_round() {
+        let mut game = create_test_game(2, 1000, 10, 0);
+        assert!(matches!(game.apply(0, Action::Call), Err(GameError::NoRoundInProgress)));
+        assert!(game.legal_actions().is_empty());
+    }
+
+    #[test]
+    fn test_legal_betting_actions() {
+        let mut game = create_test_game(2, 1000, 10, 0);
+        game.begin_round().unwrap();
+
+        assert_eq!(game.legal_actions(), vec![Action::Fold, Action::Call, Action::Raise(20), Action::AllIn]);
+
+        game.get_players_mut()[0].chips = 15;
+        assert_eq!(game.legal_actions(), vec![Action::Fold, Action::Call, Action::AllIn]);
+    }
+
+    #[test]
+    fn test_phases_advance_one_action_at_a_time() {
+        let mut game = create_test_game(2, 1000, 10, 0);
+        game.begin_round().unwrap();
+
+        game.apply(0, Action::Call).unwrap();
+        let events = game.apply(1, Action::Call).unwrap();
+        assert!(matches!(events.last(), Some(GameEvent::DrawingStarted)));
+        assert_eq!(*game.get_state(), GameState::Drawing);
+        assert_eq!(game.get_current_player(), 0);
+
+        let result = game.apply(1, Action::Draw(vec![]));
+        assert!(matches!(result, Err(GameError::InvalidAction(_))));
+
+        game.apply(0, Action::Draw(vec![0, 1])).unwrap();
+        let events = game.apply(1, Action::Draw(vec![])).unwrap();
+        assert!(matches!(events.last(), Some(GameEvent::BettingStarted { final_round: true })));
+        assert_eq!(game.legal_actions(), vec![Action::Fold, Action::Check, Action::Raise(10), Action::AllIn]);
+
+        game.apply(0, Action::Check).unwrap();
+        let events = game.apply(1, Action::Check).unwrap();
+        assert!(events.iter().any(|e| matches!(e, GameEvent::ShowdownStarted)));
+        assert!(matches!(events.last(), Some(GameEvent::RoundOver)));
+        assert!(game.is_game_over());
+        assert_eq!(game.get_pot(), 0);
+    }
+
+    #[test]
+    fn test_wrong_action_for_phase() {
+        let mut game = create_test_game(2, 1000, 10, 0);
+        game.begin_round().unwrap();
+
+        assert!(matches!(
+            game.apply(0, Action::Draw(vec![0])),
+            Err(GameError::InvalidGameState { current: GameState::Betting, expected: GameState::Drawing })
+        ));
+        game.apply(0, Action::Call).unwrap();
+        game.apply(1, Action::Call).unwrap();
+        assert!(matches!(game.apply(0, Action::Check), Err(GameError::InvalidGameState { .. })));
+    }
+
+    #[test]
+    fn test_legal_draws_follow_draw_limit() {
+        let mut game = create_test_game(2, 1000, 10, 0);
+        game.begin_round().unwrap();
+        game.apply(0, Action::Call).unwrap();
+        game.apply(1, Action::Call).unwrap();
+
+        // No Aces: stand pat or draw 1, 2 or 3 of 5 cards
+        game.set_player_hand(0, hand("2h 4c 6d 8s Th"));
+        assert_eq!(game.legal_actions().len(), 1 + 5 + 10 + 10);
+
+        // Keeping the Ace allows the four other cards to go
+        game.set_player_hand(0, hand("Ah 4c 6d 8s Th"));
+        let actions = game.legal_actions();
+        assert_eq!(actions.len(), 1 + 5 + 10 + 10 + 1);
+        assert!(actions.contains(&Action::Draw(vec![1, 2, 3, 4])));
+    }
+
+    #[test]
+    fn test_fold_ends_round_immediately() {
+        let mut game = create_test_game(2, 1000, 10, 0);
+        game.begin_round().unwrap();
+
+        game.apply(0, Action::Raise(40)).unwrap();
+        let events = game.apply(1, Action::Fold).unwrap();
+        assert!(events.iter().any(|e| matches!(e, GameEvent::PotAwarded { player: 0, amount: 40, .. })));
+        assert!(game.is_game_over());
+    }
+
+    #[test]
+    fn test_all_in_skips_to_showdown() {
+        let mut game = create_test_game(2, 1000, 10, 0);
+        game.begin_round().unwrap();
+
+        game.apply(0, Action::AllIn).unwrap();
+        game.apply(1, Action::Call).unwrap();
+        assert_eq!(*game.get_state(), GameState::Drawing);
+        game.apply(0, Action::Draw(vec![])).unwrap();
+        let events = game.apply(1, Action::Draw(vec![])).unwrap();
+
+        assert!(events.iter().any(|e| matches!(e, GameEvent::ShowdownStarted)));
+        assert!(game.is_game_over());
+        assert_eq!(game.get_players().iter().map(|p| p.chips).sum::<u32>(), 2000);
+    }
+
+    #[test]
+    fn test_rounds_can_be_replayed() {
+        let mut game = create_test_game(4, 1000, 10, 2);
+        for _ in 0..20 {
+            if game.begin_round().is_err() {
+                break;
+            }
+            let events = play_passively(&mut game);
+            assert!(matches!(events.last(), Some(GameEvent::RoundOver)));
+            assert_eq!(game.get_players().iter().map(|p| p.chips).sum::<u32>(), 4000);
+        }
+    }
+}
+
+mod seed_tests {
+    use super::*;
+
+    fn hands(game: &Game) -> Vec<String> {
+        game.get_players().iter()
+            .map(|p| format!("{:?}", p.hand))
+            .collect()
+    }
+
+    // Mixes raises, calls, checks and draws so that the deal matters
+    fn play_scripted(game: &mut Game) -> Vec<String> {
+        let mut log = Vec::new();
+        let mut step = 0;
+        while !game.is_game_over() {
+            let actions = game.legal_actions();
+            let action = match game.get_state() {
+                GameState::Drawing => actions[step % actions.len()].clone(),
+                _ if step % 5 == 0 && actions.len() == 4 => actions[2].clone(),
+                _ => actions[1].clone(),
+            };
+            game.apply(game.get_current_player(), action).unwrap();
+            log.push(format!("{:?}", hands(game)));
+            step += 1;
+        }
+        log.push(format!("{:?}", game.get_players().iter().map(|p| p.chips).collect::<Vec<_>>()));
+        log
+    }
+
+    fn seeded_game(seed: u64) -> Game {
+        let mut game = create_test_game(4, 1000, 10, 2);
+        game.set_seed(seed);
+        game
+    }
+
+    #[test]
+    fn test_same_seed_same_game() {
+        let mut first = seeded_game(2024);
+        let mut second = seeded_game(2024);
+        for _ in 0..5 {
+            first.begin_round().unwrap();
+            second.begin_round().unwrap();
+            assert_eq!(first.get_round_seed(), second.get_round_seed());
+            assert_eq!(play_scripted(&mut first), play_scripted(&mut second));
+        }
+    }
+
+    #[test]
+    fn test_rounds_get_different_seeds() {
+        let mut game = seeded_game(7);
+        let mut seeds = Vec::new();
+        for _ in 0..3 {
+            game.begin_round().unwrap();
+            seeds.push(game.get_round_seed());
+            play_scripted(&mut game);
+        }
+        seeds.dedup();
+        assert_eq!(seeds.len(), 3);
+    }
+
+    #[test]
+    fn test_round_replays_from_recorded_seed() {
+        let mut game = seeded_game(99);
+        game.begin_round().unwrap();
+        play_scripted(&mut game);
+        let events = game.begin_round().unwrap();
+        let Some(GameEvent::RoundStarted { seed, .. }) = events.first().cloned() else {
+            panic!("Round should start with its seed");
+        };
+        assert_eq!(seed, game.get_round_seed());
+        let dealt = hands(&game);
+
+        let mut replay = seeded_game(seed);
+        replay.begin_round().unwrap();
+        assert_eq!(hands(&replay), dealt);
+    }
+
+    #[test]
+    fn test_set_seed_restarts_sequence() {
+        let mut game = seeded_game(5);
+        game.begin_round().unwrap();
+        let dealt = hands(&game);
+        play_scripted(&mut game);
+
+        game.set_seed(5);
+        game.begin_round().unwrap();
+        assert_eq!(hands(&game), dealt);
+    }
+}
+
+mod stacked_deck_tests {
+    use super::*;
+
+    fn stacked(top: &str, num_jokers: u32) -> Deck {
+        Deck::stacked(hand(top).cards, num_jokers).unwrap()
+    }
+
+    fn hand_of(game: &Game, player: usize) -> String {
+        game.get_players()[player].hand.as_ref().unwrap().to_string()
+    }
+
+    #[test]
+    fn test_round_is_dealt_from_stacked_deck() {
+        let mut game = create_test_game(2, 1000, 10, 1);
+        game.set_stacked_deck(stacked("2h 2c 2s 9h Kc  As Ks Qs Js Jk", 1));
+
+        game.begin_round().unwrap();
+        assert_eq!(hand_of(&game, 0), "2h 2c 2s 9h Kc");
+        assert_eq!(hand_of(&game, 1), "As Ks Qs Js Jk");
+        assert_eq!(game.get_deck().len(), 43);
+    }
+
+    #[test]
+    fn test_stacked_reshuffle_follows_the_seed() {
+        // Ten players leave a two-card stub, so a three-card draw reshuffles
+        // the folded tenth hand
+        let reshuffled_draw = |seed: u64| {
+            let mut game = create_test_game(10, 1000, 10, 0);
+            game.set_seed(seed);
+            game.set_stacked_deck(stacked("", 0));
+            game.set_reshuffle_discards(true);
+            game.setup_test_round();
+            game.test_fold_player(9);
+            game.set_game_state(GameState::Drawing);
+            game.draw(0, &[0, 1, 2]).unwrap();
+            hand_of(&game, 0)
+        };
+        assert_eq!(reshuffled_draw(7), reshuffled_draw(7));
+        assert!((8..16).any(|seed| reshuffled_draw(seed) != reshuffled_draw(7)));
+    }
+
+    #[test]
+    fn test_scripted_scenario_plays_out() {
+        // Player 1 holds three twos and draws the fourth; player 2 holds
+        // four spades to the royal and draws a joker.
+        let mut game = create_test_game(2, 1000, 10, 1);
+        game.set_stacked_deck(stacked("2h 2c 2s 9h Kc  As Ks Qs Js 7d  2d 4c Jk", 1));
+
+        game.begin_round().unwrap();
+        game.apply(0, Action::Call).unwrap();
+        game.apply(1, Action::Call).unwrap();
+        game.apply(0, Action::Draw(vec![3, 4])).unwrap();
+        game.apply(1, Action::Draw(vec![4])).unwrap();
+        assert_eq!(hand_of(&game, 0), "2h 2c 2s 2d 4c");
+        assert_eq!(hand_of(&game, 1), "As Ks Qs Js Jk");
+
+        game.apply(0, Action::Raise(10)).unwrap();
+        game.apply(1, Action::Raise(20)).unwrap();
+        let events = game.apply(0, Action::Call).unwrap();
+
+        assert!(events.iter().any(|e| matches!(e, GameEvent::PotAwarded { player: 1, amount: 60, .. })));
+        assert_eq!(game.get_players()[0].chips, 970);
+        assert_eq!(game.get_players()[1].chips, 1030);
+    }
+
+    #[test]
+    fn test_stacked_deck_is_used_once() {
+        let mut game = create_test_game(2, 1000, 10, 0);
+        game.set_seed(3);
+        game.set_stacked_deck(stacked("", 0));
+        game.begin_round().unwrap();
+        assert_eq!(hand_of(&game, 0), "2h 3h 4h 5h 6h");
+        assert_eq!(hand_of(&game, 1), "7h 8h 9h Th Jh");
+
+        game.apply(0, Action::Fold).unwrap();
+        game.begin_round().unwrap();
+        let mut reference = create_test_game(2, 1000, 10, 0);
+        reference.set_seed(3);
+        reference.begin_round().unwrap();
+        reference.apply(0, Action::Fold).unwrap();
+        reference.begin_round().unwrap();
+        assert_eq!(hand_of(&game, 0), hand_of(&reference, 0));
+    }
+}
+
+mod forced_bet_tests {
+    use super::*;
+    use joker_poker::{ForcedBet, ForcedBets};
+
+    fn blinds(small_blind: u32, big_blind: u32) -> ForcedBets {
+        ForcedBets { small_blind, big_blind, ..ForcedBets::default() }
+    }
+
+    fn posts(events: &[GameEvent]) -> Vec<(usize, ForcedBet, u32, bool)> {
+        events.iter()
+            .filter_map(|event| match *event {
+                GameEvent::Posted { player, bet, amount, all_in } => Some((player, bet, amount, all_in)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_blinds_follow_the_button() {
+        let mut game = create_test_game(4, 1000, 10, 0);
+        game.set_forced_bets(blinds(5, 10)).unwrap();
+        let events = game.begin_round().unwrap();
+
+        assert_eq!(game.get_dealer(), 3);
+        assert_eq!(posts(&events), [(0, ForcedBet::SmallBlind, 5, false), (1, ForcedBet::BigBlind, 10, false)]);
+        assert_eq!(game.get_pot(), 15);
+        assert_eq!(game.get_current_bet(), 10);
+        assert_eq!(game.get_current_player(), 2);
+        assert_eq!(game.get_amount_to_call(0), 5);
+        assert_eq!(game.get_amount_to_call(1), 0);
+    }
+
+    #[test]
+    fn test_big_blind_has_the_option() {
+        let mut game = create_test_game(3, 1000, 10, 0);
+        game.set_forced_bets(blinds(5, 10)).unwrap();
+        game.begin_round().unwrap();
+
+        game.apply(2, Action::Call).unwrap();
+        game.apply(0, Action::Call).unwrap();
+        assert_eq!(*game.get_state(), GameState::Betting);
+        assert_eq!(game.get_current_player(), 1);
+        assert!(game.legal_actions().contains(&Action::Check));
+        game.apply(1, Action::Check).unwrap();
+        assert_eq!(*game.get_state(), GameState::Drawing);
+        assert_eq!(game.get_pot(), 30);
+    }
+
+    #[test]
+    fn test_heads_up_button_posts_small_blind_and_acts_first() {
+        let mut game = create_test_game(2, 1000, 10, 0);
+        game.set_forced_bets(blinds(5, 10)).unwrap();
+        let events = game.begin_round().unwrap();
+
+        assert_eq!(game.get_dealer(), 1);
+        assert_eq!(posts(&events), [(1, ForcedBet::SmallBlind, 5, false), (0, ForcedBet::BigBlind, 10, false)]);
+        assert_eq!(game.get_current_player(), 1);
+    }
+
+    #[test]
+    fn test_button_moves_each_round() {
+        let mut game = create_test_game(3, 1000, 10, 0);
+        for round in 0..4 {
+            let events = game.begin_round().unwrap();
+            let dealer = (2 + round) % 3;
+            assert!(matches!(events[0], GameEvent::RoundStarted { dealer: d, .. } if d == dealer));
+            assert_eq!(game.get_current_player(), (dealer + 1) % 3);
+            play_passively(&mut game);
+        }
+    }
+
+    #[test]
+    fn test_later_streets_start_after_the_button() {
+        let mut game = create_test_game(3, 1000, 10, 0);
+        game.set_dealer(0).unwrap();
+        game.begin_round().unwrap();
+        assert_eq!(game.get_current_player(), 1);
+        for player in [1, 2, 0] {
+            game.apply(player, Action::Call).unwrap();
+        }
+        assert_eq!(*game.get_state(), GameState::Drawing);
+        assert_eq!(game.get_current_player(), 1);
+        for player in [1, 2, 0] {
+            game.apply(player, Action::Draw(Vec::new())).unwrap();
+        }
+        assert_eq!(*game.get_state(), GameState::FinalBetting);
+        assert_eq!(game.get_current_player(), 1);
+    }
+
+    #[test]
+    fn test_button_skips_busted_seats() {
+        let mut game = create_test_game(3, 1000, 10, 0);
+        game.get_players_mut()[0].chips = 0;
+        game.begin_round().unwrap();
+        play_passively(&mut game);
+        assert_eq!(game.get_dealer(), 1);
+    }
+
+    #[test]
+    fn test_short_blind_posts_all_in() {
+        let mut game = create_test_game(3, 1000, 10, 0);
+        game.get_players_mut()[1].chips = 4;
+        game.set_forced_bets(blinds(5, 10)).unwrap();
+        let events = game.begin_round().unwrap();
+
+        assert_eq!(posts(&events)[1], (1, ForcedBet::BigBlind, 4, true));
+        assert_eq!(game.get_players()[1].chips, 0);
+        // The others still have to match the full big blind
+        assert_eq!(game.get_current_bet(), 10);
+        game.apply(2, Action::Call).unwrap();
+        game.apply(0, Action::Call).unwrap();
+        assert_eq!(*game.get_state(), GameState::Drawing);
+        assert_eq!(game.side_pots()[0].amount, 12);
+        assert_eq!(game.side_pots()[1].amount, 12);
+    }
+
+    #[test]
+    fn test_blinds_that_put_everyone_all_in_skip_betting() {
+        let mut game = create_test_game(2, 1000, 10, 0);
+        game.get_players_mut()[0].chips = 10;
+        game.get_players_mut()[1].chips = 5;
+        game.set_forced_bets(blinds(5, 10)).unwrap();
+        game.begin_round().unwrap();
+        assert_eq!(*game.get_state(), GameState::Drawing);
+        play_passively(&mut game);
+        let chips: u32 = game.get_players().iter().map(|p| p.chips).sum();
+        assert_eq!(chips, 15);
+    }
+
+    #[test]
+    fn test_antes() {
+        let mut game = create_test_game(3, 1000, 10, 0);
+        game.get_players_mut()[2].chips = 3;
+        game.set_forced_bets(ForcedBets { ante: 5, ..ForcedBets::default() }).unwrap();
+        let events = game.begin_round().unwrap();
+
+        assert_eq!(posts(&events), [
+            (0, ForcedBet::Ante, 5, false),
+            (1, ForcedBet::Ante, 5, false),
+            (2, ForcedBet::Ante, 3, true),
+        ]);
+        assert_eq!(game.get_pot(), 13);
+        assert_eq!(game.get_round_bet(2), 3);
+        // Antes don't count towards the opening bet
+        assert_eq!(game.get_street_bet(0), 0);
+        assert_eq!(game.get_current_bet(), 10);
+        assert_eq!(game.get_current_player(), 0);
+    }
+
+    #[test]
+    fn test_bring_in() {
+        let mut game = create_test_game(3, 1000, 10, 0);
+        game.set_forced_bets(ForcedBets { bring_in: 3, ..ForcedBets::default() }).unwrap();
+        let events = game.begin_round().unwrap();
+
+        assert_eq!(posts(&events), [(0, ForcedBet::BringIn, 3, false)]);
+        assert_eq!(game.get_current_bet(), 3);
+        assert_eq!(game.get_current_player(), 1);
+        game.apply(1, Action::Call).unwrap();
+        game.apply(2, Action::Call).unwrap();
+        // Nobody raised, so the bring-in does not act again
+        assert_eq!(*game.get_state(), GameState::Drawing);
+        assert_eq!(game.get_pot(), 9);
+    }
+
+    #[test]
+    fn test_forced_bet_validation() {
+        let mut game = create_test_game(3, 1000, 10, 0);
+        assert!(matches!(game.set_forced_bets(blinds(10, 5)), Err(GameError::InvalidBet(_))));
+        assert_eq!(game.get_forced_bets(), ForcedBets::default());
+        assert!(matches!(game.set_dealer(3), Err(GameError::PlayerNotFound(_))));
+    }
+}
+
+mod variant_tests {
+    use super::*;
+    use joker_poker::{BetLimit, Card, HandEvaluation, JokerDraw, Step, Variant};
+
+    // Two cards each and three on the board, scored as one five-card hand
+    struct BoardGame(BetLimit);
+
+    impl Variant for BoardGame {
+        fn name(&self) -> &'static str {
+            "board-game"
+        }
+
+        fn steps(&self) -> Vec<Step> {
+            vec![Step::Deal { count: 2 }, Step::Betting, Step::Board { count: 3 }, Step::Betting]
+        }
+
+        fn evaluate(&self, hole: &Hand, board: &[Card]) -> HandEvaluation {
+            Hand::new(hole.cards.iter().chain(board).copied().collect()).evaluation()
+        }
+
+        fn bet_limit(&self) -> BetLimit {
+            self.0
+        }
+    }
+
+    #[test]
+    fn test_joker_draw_is_the_default() {
+        let game = create_test_game(2, 1000, 10, 0);
+        assert_eq!(game.get_variant().name(), "joker-draw");
+        assert_eq!(JokerDraw.steps(), [Step::Deal { count: 5 }, Step::Betting, Step::Draw, Step::Betting]);
+        assert!(game.get_board().is_empty());
+    }
+
+    #[test]
+    fn test_game_can_move_between_threads() {
+        fn assert_send<T: Send>(_: &T) {}
+        let mut game = create_test_game(2, 1000, 10, 0);
+        game.set_variant(Box::new(BoardGame(BetLimit::NoLimit))).unwrap();
+        assert_send(&game);
+        let game = std::thread::spawn(move || game).join().unwrap();
+        assert_eq!(game.get_variant().name(), "board-game");
+    }
+
+    #[test]
+    fn test_variant_steps_are_played_in_order() {
+        let mut game = stacked_game(BoardGame(BetLimit::NoLimit), 2, "As Ah Kd Kc Ad 7s 2c");
+        game.begin_round().unwrap();
+        assert_eq!(game.get_players()[0].hand.as_ref().unwrap().cards, hand("As Ah").cards);
+        assert_eq!(game.get_players()[1].hand.as_ref().unwrap().cards, hand("Kd Kc").cards);
+        assert!(game.get_board().is_empty());
+        assert_eq!(*game.get_state(), GameState::Betting);
+
+        game.apply(0, Action::Call).unwrap();
+        let events = game.apply(1, Action::Call).unwrap();
+        assert!(events.iter().any(|e| matches!(e, GameEvent::BoardDealt { cards } if *cards == hand("Ad 7s 2c").cards)));
+        assert_eq!(game.get_board(), &hand("Ad 7s 2c").cards[..]);
+        assert_eq!(*game.get_state(), GameState::FinalBetting);
+        assert_eq!(game.get_current_bet(), 0);
+
+        game.apply(0, Action::Check).unwrap();
+        let events = game.apply(1, Action::Check).unwrap();
+        assert!(events.iter().any(|e| matches!(e, GameEvent::PotAwarded { player: 0, amount: 20, .. })));
+        assert!(game.is_game_over());
+    }
+
+    #[test]
+    fn test_variant_without_draw_rejects_draws() {
+        let mut game = stacked_game(BoardGame(BetLimit::NoLimit), 2, "As Ah Kd Kc Ad 7s 2c");
+        game.begin_round().unwrap();
+        game.set_game_state(GameState::Drawing);
+        assert!(matches!(game.draw(0, &[]), Err(GameError::InvalidDraw(_))));
+    }
+
+    #[test]
+    fn test_round_needs_enough_cards() {
+        let mut game = create_test_game(6, 1000, 10, 0);
+        game.set_variant(Box::new(ManyCards)).unwrap();
+        assert!(matches!(game.begin_round(), Err(GameError::InvalidAction(_))));
+        assert!(!game.is_round_active());
+    }
+
+    // Needs more cards than a deck holds with six players
+    struct ManyCards;
+
+    impl Variant for ManyCards {
+        fn name(&self) -> &'static str {
+            "many-cards"
+        }
+
+        fn steps(&self) -> Vec<Step> {
+            vec![Step::Deal { count: 9 }, Step::Betting]
+        }
+
+        fn evaluate(&self, hole: &Hand, _board: &[Card]) -> HandEvaluation {
+            hole.evaluation()
+        }
+    }
+
+    #[test]
+    fn test_variant_cannot_change_during_a_round() {
+        let mut game = create_test_game(2, 1000, 10, 0);
+        game.begin_round().unwrap();
+        assert!(matches!(game.set_variant(Box::new(BoardGame(BetLimit::NoLimit))), Err(GameError::RoundInProgress)));
+        assert_eq!(game.get_variant().name(), "joker-draw");
+    }
+
+    #[test]
+    fn test_pot_limit_caps_raises() {
+        let mut game = stacked_game(BoardGame(BetLimit::PotLimit), 3, "As Ah Kd Kc Qd Qc Ad 7s 2c");
+        game.begin_round().unwrap();
+
+        // The first player pays 10 into an empty pot: at most 10 + 10
+        assert_eq!(game.get_max_bet(0), 20);
+        assert!(!game.legal_actions().contains(&Action::AllIn));
+        assert!(matches!(game.apply(0, Action::Raise(30)), Err(GameError::BetOutOfRange { max: 20, .. })));
+        assert!(game.apply(0, Action::AllIn).is_err());
+        game.apply(0, Action::Raise(20)).unwrap();
+
+        // Calling 20 makes the pot 40, so the next raise is to at most 60
+        assert_eq!(game.get_max_bet(1), 60);
+        assert_eq!(game.table_view(1).max_bet, 60);
+        game.apply(1, Action::Raise(60)).unwrap();
+        assert_eq!(game.get_pot(), 80);
+    }
+
+    #[test]
+    fn test_pot_limit_allows_all_in_below_the_cap() {
+        let mut game = stacked_game(BoardGame(BetLimit::PotLimit), 2, "As Ah Kd Kc Ad 7s 2c");
+        game.get_players_mut()[0].chips = 15;
+        game.begin_round().unwrap();
+        assert_eq!(game.get_max_bet(0), 15);
+        assert!(game.legal_actions().contains(&Action::AllIn));
+        game.apply(0, Action::AllIn).unwrap();
+    }
+}
@@ -1,49 +1,26 @@
 use joker_poker::{Hand, Card, Rank, Suit, HandRank};
 
-// Helper function to create test hands
-fn create_test_hand(cards: Vec<(Rank, Suit, bool)>) -> Hand {
-    let cards = cards.into_iter()
-        .map(|(rank, suit, is_joker)| Card::new(rank, suit, is_joker))
-        .collect();
-    Hand::new(cards)
-}
+mod common;
+use common::*;
 
 mod royal_flush_tests {
     use super::*;
 
     #[test]
     fn test_natural_royal_flush() {
-        let hand = create_test_hand(vec![
-            (Rank::Ten, Suit::Hearts, false),
-            (Rank::Jack, Suit::Hearts, false),
-            (Rank::Queen, Suit::Hearts, false),
-            (Rank::King, Suit::Hearts, false),
-            (Rank::Ace, Suit::Hearts, false),
-        ]);
+        let hand = hand("Th Jh Qh Kh Ah");
         assert_eq!(hand.evaluate(), HandRank::RoyalFlush);
     }
 
     #[test]
     fn test_royal_flush_with_one_joker() {
-        let hand = create_test_hand(vec![
-            (Rank::Ten, Suit::Hearts, false),
-            (Rank::Jack, Suit::Hearts, false),
-            (Rank::Queen, Suit::Hearts, false),
-            (Rank::King, Suit::Hearts, false),
-            (Rank::Ace, Suit::Hearts, true),  // Joker
-        ]);
+        let hand = hand("Th Jh Qh Kh Jk");
         assert_eq!(hand.evaluate(), HandRank::RoyalFlush);
     }
 
     #[test]
     fn test_royal_flush_with_two_jokers() {
-        let hand = create_test_hand(vec![
-            (Rank::Ten, Suit::Hearts, false),
-            (Rank::Jack, Suit::Hearts, false),
-            (Rank::Queen, Suit::Hearts, false),
-            (Rank::Ace, Suit::Hearts, true),  // Joker
-            (Rank::King, Suit::Hearts, true), // Joker
-        ]);
+        let hand = hand("Th Jh Qh Jk Jk");
         assert_eq!(hand.evaluate(), HandRank::RoyalFlush);
     }
 }
@@ -53,25 +30,13 @@ mod five_of_a_kind_tests {
 
     #[test]
     fn test_five_of_kind_with_one_joker() {
-        let hand = create_test_hand(vec![
-            (Rank::Ace, Suit::Hearts, false),
-            (Rank::Ace, Suit::Diamonds, false),
-            (Rank::Ace, Suit::Clubs, false),
-            (Rank::Ace, Suit::Spades, false),
-            (Rank::Ace, Suit::Hearts, true),  // Joker
-        ]);
+        let hand = hand("Ah Ad Ac As Jk");
         assert_eq!(hand.evaluate(), HandRank::FiveOfAKind);
     }
 
     #[test]
     fn test_five_of_kind_with_two_jokers() {
-        let hand = create_test_hand(vec![
-            (Rank::Ace, Suit::Hearts, false),
-            (Rank::Ace, Suit::Diamonds, false),
-            (Rank::Ace, Suit::Clubs, false),
-            (Rank::Ace, Suit::Hearts, true),  // Joker
-            (Rank::Ace, Suit::Hearts, true),  // Joker
-        ]);
+        let hand = hand("Ah Ad Ac Jk Jk");
         assert_eq!(hand.evaluate(), HandRank::FiveOfAKind);
     }
 }
@@ -81,37 +46,19 @@ mod straight_flush_tests {
 
     #[test]
     fn test_natural_straight_flush() {
-        let hand = create_test_hand(vec![
-            (Rank::Five, Suit::Hearts, false),
-            (Rank::Six, Suit::Hearts, false),
-            (Rank::Seven, Suit::Hearts, false),
-            (Rank::Eight, Suit::Hearts, false),
-            (Rank::Nine, Suit::Hearts, false),
-        ]);
+        let hand = hand("5h 6h 7h 8h 9h");
         assert_eq!(hand.evaluate(), HandRank::StraightFlush);
     }
 
     #[test]
     fn test_straight_flush_with_joker() {
-        let hand = create_test_hand(vec![
-            (Rank::Five, Suit::Hearts, false),
-            (Rank::Six, Suit::Hearts, false),
-            (Rank::Seven, Suit::Hearts, false),
-            (Rank::Eight, Suit::Hearts, false),
-            (Rank::Nine, Suit::Hearts, true),  // Joker
-        ]);
+        let hand = hand("5h 6h 7h 8h Jk");
         assert_eq!(hand.evaluate(), HandRank::StraightFlush);
     }
 
     #[test]
     fn test_ace_low_straight_flush() {
-        let hand = create_test_hand(vec![
-            (Rank::Ace, Suit::Hearts, false),
-            (Rank::Two, Suit::Hearts, false),
-            (Rank::Three, Suit::Hearts, false),
-            (Rank::Four, Suit::Hearts, false),
-            (Rank::Five, Suit::Hearts, false),
-        ]);
+        let hand = hand("Ah 2h 3h 4h 5h");
         assert_eq!(hand.evaluate(), HandRank::StraightFlush);
     }
 }
@@ -121,25 +68,13 @@ mod four_of_a_kind_tests {
 
     #[test]
     fn test_natural_four_of_kind() {
-        let hand = create_test_hand(vec![
-            (Rank::Ace, Suit::Hearts, false),
-            (Rank::Ace, Suit::Diamonds, false),
-            (Rank::Ace, Suit::Clubs, false),
-            (Rank::Ace, Suit::Spades, false),
-            (Rank::King, Suit::Hearts, false),
-        ]);
+        let hand = hand("Ah Ad Ac As Kh");
         assert_eq!(hand.evaluate(), HandRank::FourOfAKind);
     }
 
     #[test]
     fn test_four_of_kind_with_joker() {
-        let hand = create_test_hand(vec![
-            (Rank::Ace, Suit::Hearts, false),
-            (Rank::Ace, Suit::Diamonds, false),
-            (Rank::Ace, Suit::Clubs, false),
-            (Rank::King, Suit::Hearts, false),
-            (Rank::Ace, Suit::Hearts, true),  // Joker
-        ]);
+        let hand = hand("Ah Ad Ac Kh Jk");
         assert_eq!(hand.evaluate(), HandRank::FourOfAKind);
     }
 }
@@ -149,25 +84,13 @@ mod full_house_tests {
 
     #[test]
     fn test_natural_full_house() {
-        let hand = create_test_hand(vec![
-            (Rank::Ace, Suit::Hearts, false),
-            (Rank::Ace, Suit::Diamonds, false),
-            (Rank::Ace, Suit::Clubs, false),
-            (Rank::King, Suit::Hearts, false),
-            (Rank::King, Suit::Diamonds, false),
-        ]);
+        let hand = hand("Ah Ad Ac Kh Kd");
         assert_eq!(hand.evaluate(), HandRank::FullHouse);
     }
 
     #[test]
     fn test_full_house_with_joker() {
-        let hand = create_test_hand(vec![
-            (Rank::Ace, Suit::Hearts, false),
-            (Rank::Ace, Suit::Diamonds, false),
-            (Rank::King, Suit::Hearts, false),
-            (Rank::King, Suit::Diamonds, false),
-            (Rank::Ace, Suit::Hearts, true),  // Joker
-        ]);
+        let hand = hand("Ah Ad Kh Kd Jk");
         assert_eq!(hand.evaluate(), HandRank::FullHouse);
     }
 }
@@ -177,25 +100,13 @@ mod flush_tests {
 
     #[test]
     fn test_natural_flush() {
-        let hand = create_test_hand(vec![
-            (Rank::Two, Suit::Hearts, false),
-            (Rank::Five, Suit::Hearts, false),
-            (Rank::Seven, Suit::Hearts, false),
-            (Rank::Jack, Suit::Hearts, false),
-            (Rank::King, Suit::Hearts, false),
-        ]);
+        let hand = hand("2h 5h 7h Jh Kh");
         assert_eq!(hand.evaluate(), HandRank::Flush);
     }
 
     #[test]
     fn test_flush_with_joker() {
-        let hand = create_test_hand(vec![
-            (Rank::Two, Suit::Hearts, false),
-            (Rank::Five, Suit::Hearts, false),
-            (Rank::Seven, Suit::Hearts, false),
-            (Rank::Jack, Suit::Hearts, false),
-            (Rank::King, Suit::Hearts, true),  // Joker
-        ]);
+        let hand = hand("2h 5h 7h Jh Jk");
         assert_eq!(hand.evaluate(), HandRank::Flush);
     }
 }
@@ -205,49 +116,37 @@ mod straight_tests {
 
     #[test]
     fn test_natural_straight() {
-        let hand = create_test_hand(vec![
-            (Rank::Five, Suit::Hearts, false),
-            (Rank::Six, Suit::Diamonds, false),
-            (Rank::Seven, Suit::Clubs, false),
-            (Rank::Eight, Suit::Hearts, false),
-            (Rank::Nine, Suit::Spades, false),
-        ]);
+        let hand = hand("5h 6d 7c 8h 9s");
         assert_eq!(hand.evaluate(), HandRank::Straight);
     }
 
     #[test]
     fn test_straight_with_joker() {
-        let hand = create_test_hand(vec![
-            (Rank::Five, Suit::Hearts, false),
-            (Rank::Six, Suit::Diamonds, false),
-            (Rank::Seven, Suit::Clubs, false),
-            (Rank::Eight, Suit::Hearts, false),
-            (Rank::Nine, Suit::Hearts, true),  // Joker
-        ]);
+        let hand = hand("5h 6d 7c 8h Jk");
         assert_eq!(hand.evaluate(), HandRank::Straight);
     }
 
     #[test]
     fn test_ace_low_straight() {
-        let hand = create_test_hand(vec![
-            (Rank::Ace, Suit::Hearts, false),
-            (Rank::Two, Suit::Diamonds, false),
-            (Rank::Three, Suit::Clubs, false),
-            (Rank::Four, Suit::Hearts, false),
-            (Rank::Five, Suit::Spades, false),
-        ]);
+        let hand = hand("Ah 2d 3c 4h 5s");
         assert_eq!(hand.evaluate(), HandRank::Straight);
     }
 
+    #[test]
+    fn test_no_wraparound_straight() {
+        let hand = hand("Kh Ad 2c 3h 4s");
+        assert_eq!(hand.evaluate(), HandRank::HighCard);
+    }
+
+    #[test]
+    fn test_no_wraparound_straight_with_joker() {
+        let hand = hand("Qh Kd 2c 3h Jk");
+        assert_eq!(hand.evaluate(), HandRank::OnePair);
+    }
+
     #[test]
     fn test_straight_with_joker_in_middle() {
-        let hand = create_test_hand(vec![
-            (Rank::Five, Suit::Hearts, false),
-            (Rank::Six, Suit::Diamonds, false),
-            (Rank::Ace, Suit::Clubs, true),  // Joker
-            (Rank::Eight, Suit::Hearts, false),
-            (Rank::Nine, Suit::Hearts, false),  
-        ]);
+        let hand = hand("5h 6d Jk 8h 9h");
         assert_eq!(hand.evaluate(), HandRank::Straight);
     }
 }
@@ -257,25 +156,13 @@ mod three_of_a_kind_tests {
 
     #[test]
     fn test_natural_three_of_kind() {
-        let hand = create_test_hand(vec![
-            (Rank::Ace, Suit::Hearts, false),
-            (Rank::Ace, Suit::Diamonds, false),
-            (Rank::Ace, Suit::Clubs, false),
-            (Rank::King, Suit::Hearts, false),
-            (Rank::Queen, Suit::Diamonds, false),
-        ]);
+        let hand = hand("Ah Ad Ac Kh Qd");
         assert_eq!(hand.evaluate(), HandRank::ThreeOfAKind);
     }
 
     #[test]
     fn test_three_of_kind_with_joker() {
-        let hand = create_test_hand(vec![
-            (Rank::Ace, Suit::Hearts, false),
-            (Rank::Ace, Suit::Diamonds, false),
-            (Rank::King, Suit::Hearts, false),
-            (Rank::Queen, Suit::Diamonds, false),
-            (Rank::Ace, Suit::Hearts, true),  // Joker
-        ]);
+        let hand = hand("Ah Ad Kh Qd Jk");
         assert_eq!(hand.evaluate(), HandRank::ThreeOfAKind);
     }
 }
@@ -285,25 +172,13 @@ mod two_pair_tests {
 
     #[test]
     fn test_natural_two_pair() {
-        let hand = create_test_hand(vec![
-            (Rank::Ace, Suit::Hearts, false),
-            (Rank::Ace, Suit::Diamonds, false),
-            (Rank::King, Suit::Hearts, false),
-            (Rank::King, Suit::Diamonds, false),
-            (Rank::Queen, Suit::Hearts, false),
-        ]);
+        let hand = hand("Ah Ad Kh Kd Qh");
         assert_eq!(hand.evaluate(), HandRank::TwoPair);
     }
 
     #[test]
     fn test_two_pair_with_joker() {
-        let hand = create_test_hand(vec![
-            (Rank::Ace, Suit::Hearts, false),
-            (Rank::Ace, Suit::Diamonds, false),
-            (Rank::King, Suit::Hearts, false),
-            (Rank::King, Suit::Diamonds, false),
-            (Rank::Queen, Suit::Hearts, true),  // Joker
-        ]);
+        let hand = hand("Ah Ad Kh Kd Jk");
         // With a joker and two pairs, this should evaluate to a Full House
         // because that's the best possible hand with these cards
         assert_eq!(hand.evaluate(), HandRank::FullHouse);
@@ -315,25 +190,13 @@ mod one_pair_tests {
 
     #[test]
     fn test_natural_one_pair() {
-        let hand = create_test_hand(vec![
-            (Rank::Ace, Suit::Hearts, false),
-            (Rank::Ace, Suit::Diamonds, false),
-            (Rank::King, Suit::Hearts, false),
-            (Rank::Queen, Suit::Hearts, false),
-            (Rank::Jack, Suit::Hearts, false),
-        ]);
+        let hand = hand("Ah Ad Kh Qh Jh");
         assert_eq!(hand.evaluate(), HandRank::OnePair);
     }
 
     #[test]
     fn test_one_pair_with_joker() {
-        let hand = create_test_hand(vec![
-            (Rank::Ace, Suit::Hearts, false),
-            (Rank::Ace, Suit::Diamonds, false),
-            (Rank::King, Suit::Hearts, false),
-            (Rank::Queen, Suit::Hearts, false),
-            (Rank::Ace, Suit::Hearts, true),  // Joker
-        ]);
+        let hand = hand("Ah Ad Kh Qh Jk");
         // With a pair and a joker, this should evaluate to Three of a Kind
         // because that's the best possible hand with these cards
         assert_eq!(hand.evaluate(), HandRank::ThreeOfAKind);
@@ -345,13 +208,7 @@ mod high_card_tests {
 
     #[test]
     fn test_high_card() {
-        let hand = create_test_hand(vec![
-            (Rank::Ace, Suit::Hearts, false),
-            (Rank::King, Suit::Diamonds, false),
-            (Rank::Queen, Suit::Clubs, false),
-            (Rank::Jack, Suit::Hearts, false),
-            (Rank::Nine, Suit::Spades, false),
-        ]);
+        let hand = hand("Ah Kd Qc Jh 9s");
         assert_eq!(hand.evaluate(), HandRank::HighCard);
     }
 }
@@ -361,42 +218,412 @@ mod edge_cases {
 
     #[test]
     fn test_all_jokers() {
-        let hand = create_test_hand(vec![
-            (Rank::Ace, Suit::Hearts, true),
-            (Rank::Ace, Suit::Hearts, true),
-            (Rank::Ace, Suit::Hearts, true),
-            (Rank::Ace, Suit::Hearts, true),
-            (Rank::Ace, Suit::Hearts, true),
-        ]);
+        let hand = hand("Jk Jk Jk Jk Jk");
         // Five jokers should make the best possible hand: Five of a Kind
         assert_eq!(hand.evaluate(), HandRank::FiveOfAKind);
     }
 
     #[test]
     fn test_four_jokers() {
-        let hand = create_test_hand(vec![
-            (Rank::Ace, Suit::Hearts, false),
-            (Rank::Ace, Suit::Hearts, true),
-            (Rank::Ace, Suit::Hearts, true),
-            (Rank::Ace, Suit::Hearts, true),
-            (Rank::Ace, Suit::Hearts, true),
-        ]);
+        let hand = hand("Ah Jk Jk Jk Jk");
         assert_eq!(hand.evaluate(), HandRank::FiveOfAKind);
     }
 
     #[test]
     fn test_empty_hand() {
-        let hand = create_test_hand(vec![]);
+        let hand = hand("");
         assert_eq!(hand.evaluate(), HandRank::HighCard);
     }
 
     #[test]
     fn test_invalid_hand_size() {
-        let hand = create_test_hand(vec![
-            (Rank::Ace, Suit::Hearts, false),
-            (Rank::King, Suit::Hearts, false),
-            (Rank::Queen, Suit::Hearts, false),
-        ]);
+        let hand = hand("Ah Kh Qh");
         assert_eq!(hand.evaluate(), HandRank::HighCard);
     }
-}
\ No newline at end of file
+}
+mod hand_value_tests {
+    use super::*;
+
+    #[test]
+    fn test_higher_pair_wins() {
+        let aces = hand("Ah Ad 7c 5h 3s");
+        let twos = hand("2h 2d Kc Qh Js");
+        assert!(aces.value() > twos.value());
+    }
+
+    #[test]
+    fn test_kicker_breaks_tie() {
+        let king_kicker = hand("9h 9d Kc 5h 3s");
+        let queen_kicker = hand("9c 9s Qc 5d 3h");
+        assert!(king_kicker.value() > queen_kicker.value());
+    }
+
+    #[test]
+    fn test_identical_ranks_tie() {
+        let first = hand("Ah Ad Kh Kd Qh");
+        let second = hand("Ac As Kc Ks Qc");
+        assert_eq!(first.value(), second.value());
+    }
+
+    #[test]
+    fn test_category_beats_kickers() {
+        let two_pair = hand("3h 3d 2h 2d 4h");
+        let aces = hand("Ah Ad Kc Qh Js");
+        assert!(two_pair.value() > aces.value());
+    }
+
+    #[test]
+    fn test_joker_takes_best_substitute() {
+        // The joker pairs the king rather than any lower card
+        let hand = hand("Kh 9d 7c 4h Jk");
+        let value = hand.value();
+        assert_eq!(value.category, HandRank::OnePair);
+        assert_eq!(value.tiebreak, vec![Rank::King, Rank::Nine, Rank::Seven, Rank::Four]);
+    }
+
+    #[test]
+    fn test_natural_beats_joker_hand_of_lower_rank() {
+        let natural = hand("Qh Qd Qc 4h 2s");
+        let with_joker = hand("Jh Jd 9c 4d Jk");
+        assert_eq!(with_joker.value().category, HandRank::ThreeOfAKind);
+        assert!(natural.value() > with_joker.value());
+    }
+}
+
+mod joker_assignment_tests {
+    use super::*;
+
+    #[test]
+    fn test_joker_fills_straight_gap() {
+        let hand = hand("5h 6d Jk 8h 9h");
+        let evaluation = hand.evaluation();
+        assert_eq!(evaluation.value.category, HandRank::Straight);
+        assert_eq!(evaluation.jokers.len(), 1);
+        assert_eq!(evaluation.jokers[0].index, 2);
+        assert_eq!(evaluation.jokers[0].rank, Rank::Seven);
+        assert_eq!(evaluation.cards.len(), 5);
+    }
+
+    #[test]
+    fn test_jokers_complete_five_of_a_kind() {
+        let hand = hand("9h 9d 9c Jk Jk");
+        let evaluation = hand.evaluation();
+        assert_eq!(evaluation.value.category, HandRank::FiveOfAKind);
+        let indices: Vec<usize> = evaluation.jokers.iter().map(|j| j.index).collect();
+        assert_eq!(indices, vec![3, 4]);
+        assert!(evaluation.jokers.iter().all(|j| j.rank == Rank::Nine));
+        assert_eq!(evaluation.jokers[0].suit, Suit::Spades);
+    }
+
+    #[test]
+    fn test_flush_joker_keeps_suit() {
+        let hand = hand("2s 5s 7s Js Jk");
+        let evaluation = hand.evaluation();
+        assert_eq!(evaluation.value.category, HandRank::Flush);
+        assert_eq!(evaluation.jokers[0].suit, Suit::Spades);
+        assert_eq!(evaluation.jokers[0].rank, Rank::Ace);
+    }
+
+    #[test]
+    fn test_resolved_cards_replace_jokers() {
+        let hand = hand("Kh Jk 7c 4d 2s");
+        let resolved = hand.evaluation().resolved_cards();
+        assert!(resolved.iter().all(|c| !c.is_joker()));
+        assert_eq!(resolved[1].rank(), Some(Rank::King));
+        assert_eq!(Hand::new(resolved).value(), hand.value());
+    }
+
+    #[test]
+    fn test_assignment_display() {
+        let hand = hand("5s 6s 7s 8s Jk");
+        let evaluation = hand.evaluation();
+        assert_eq!(evaluation.value.category, HandRank::StraightFlush);
+        assert_eq!(evaluation.jokers[0].to_string(), "Joker as 9♠");
+    }
+
+    #[test]
+    fn test_no_jokers_no_assignments() {
+        let hand = hand("Ah Kd Qc Jh 9s");
+        assert!(hand.evaluation().jokers.is_empty());
+    }
+}
+
+mod best_five_tests {
+    use super::*;
+
+    #[test]
+    fn test_best_five_from_seven() {
+        // A flush hides among a pair and some board cards
+        let hand = hand("Kc Kh 2h 7h 9s Jh 4h");
+        let best = hand.best_five().expect("seven cards should evaluate");
+        assert_eq!(best.value.category, HandRank::Flush);
+        assert_eq!(best.cards.len(), 5);
+        assert!(best.cards.iter().all(|c| c.suit() == Some(Suit::Hearts)));
+        assert_eq!(best.value.tiebreak, vec![Rank::King, Rank::Jack, Rank::Seven, Rank::Four, Rank::Two]);
+    }
+
+    #[test]
+    fn test_best_five_keeps_best_kickers() {
+        let hand = hand("Ac Ah 2d Qs 3h Tc");
+        let best = hand.best_five().unwrap();
+        assert_eq!(best.value.category, HandRank::OnePair);
+        assert_eq!(best.value.tiebreak, vec![Rank::Ace, Rank::Queen, Rank::Ten, Rank::Three]);
+    }
+
+    #[test]
+    fn test_best_five_joker_index_refers_to_hand() {
+        let hand = hand("2c 6d 7s 3h 8h Jk 9c");
+        let best = hand.best_five().unwrap();
+        assert_eq!(best.value.category, HandRank::Straight);
+        assert_eq!(best.value.tiebreak, vec![Rank::Ten]);
+        assert_eq!(best.jokers.len(), 1);
+        assert_eq!(best.jokers[0].index, 5);
+        assert_eq!(best.jokers[0].rank, Rank::Ten);
+    }
+
+    #[test]
+    fn test_best_five_nine_cards() {
+        let hand = hand("2c 2d 2s 5h 5c Kh Kc 9d Js");
+        let best = hand.best_five().unwrap();
+        assert_eq!(best.value.category, HandRank::FullHouse);
+        assert_eq!(best.value.tiebreak, vec![Rank::Two, Rank::King]);
+    }
+
+    #[test]
+    fn test_best_five_rejects_bad_sizes() {
+        let four = hand("Ac Ah 2d Qs");
+        assert!(four.best_five().is_none());
+
+        let ten = Hand::new(Rank::ALL[..10].iter()
+            .map(|&rank| Card::new(rank, Suit::Clubs))
+            .collect());
+        assert!(ten.best_five().is_none());
+    }
+
+    #[test]
+    fn test_value_uses_best_five_for_large_hands() {
+        let hand = hand("Ac Ah Ad As 3h Tc");
+        assert_eq!(hand.value(), hand.best_five().unwrap().value);
+        assert_eq!(hand.value().tiebreak, vec![Rank::Ace, Rank::Ten]);
+    }
+
+    #[test]
+    fn test_best_five_using_two_of_the_first_four() {
+        // Four hearts in the first four cards make no flush with only two
+        // of them allowed
+        let hand = hand("Ah Kh Qh Jh 2h 7c 9s 3d Td");
+        let best = hand.best_five_using(4, 2).unwrap();
+        assert_eq!(best.value.category, HandRank::HighCard);
+        assert_eq!(best.value.tiebreak, vec![Rank::Ace, Rank::King, Rank::Ten, Rank::Nine, Rank::Seven]);
+        assert_eq!(hand.best_five().unwrap().value.category, HandRank::Flush);
+    }
+
+    #[test]
+    fn test_best_five_using_jokers_on_either_side() {
+        let straight = hand("Jk 9c 2d 3c Ts Js Qs 4h 5h");
+        let best = straight.best_five_using(4, 2).unwrap();
+        assert_eq!(best.value.category, HandRank::Straight);
+        assert_eq!(best.value.tiebreak, vec![Rank::King]);
+        assert_eq!(best.jokers[0].index, 0);
+
+        // A board joker still leaves the two hole cards to be played
+        let full_house = hand("As Ad 7c 8c Jk Kh Kd 2s 3h");
+        let best = full_house.best_five_using(4, 2).unwrap();
+        assert_eq!(best.value.category, HandRank::FullHouse);
+        assert_eq!(best.value.tiebreak, vec![Rank::Ace, Rank::King]);
+    }
+
+    #[test]
+    fn test_best_five_using_needs_enough_cards() {
+        let hand = hand("Ah Kh Qh Jh 2h 7c");
+        assert!(hand.best_five_using(4, 2).is_none());
+        assert!(hand.best_five_using(1, 2).is_none());
+        assert!(hand.best_five_using(4, 6).is_none());
+        assert!(hand.best_five_using(7, 2).is_none());
+    }
+}
+
+mod visibility_tests {
+    use super::*;
+
+    #[test]
+    fn test_cards_are_dealt_face_down() {
+        let mut hand = hand("Ah Kd");
+        hand.add_cards(vec![Card::new(Rank::Two, Suit::Clubs)]);
+        assert!((0..3).all(|i| !hand.is_face_up(i)));
+        assert!(hand.visible().cards.is_empty());
+    }
+
+    #[test]
+    fn test_face_up_cards_are_visible() {
+        let mut hand = hand("Ah Kd");
+        hand.add_face_up(vec![Card::new(Rank::Two, Suit::Clubs), Card::new(Rank::Nine, Suit::Hearts)]);
+        hand.add_cards(vec![Card::new(Rank::Four, Suit::Spades)]);
+        assert_eq!(hand.visible().cards, super::hand("2c 9h").cards);
+        assert!(!hand.is_face_up(1));
+        assert!(hand.is_face_up(2));
+        assert!(!hand.is_face_up(4));
+        assert!(!hand.is_face_up(5));
+
+        hand.set_face_up(0, true);
+        hand.set_face_up(2, false);
+        assert_eq!(hand.visible().cards, super::hand("Ah 9h").cards);
+    }
+
+    #[test]
+    fn test_discard_keeps_visibility_in_place() {
+        let mut hand = hand("Ah Kd");
+        hand.add_face_up(vec![Card::new(Rank::Two, Suit::Clubs), Card::new(Rank::Nine, Suit::Hearts)]);
+        hand.discard(&[0, 2]);
+        assert_eq!(hand.cards, super::hand("Kd 9h").cards);
+        assert!(!hand.is_face_up(0));
+        assert!(hand.is_face_up(1));
+    }
+
+    #[test]
+    fn test_partial_hands() {
+        let value = |notation: &str| {
+            let mut shown = Hand::new(Vec::new());
+            shown.add_face_up(hand(notation).cards);
+            shown.visible_value()
+        };
+        assert_eq!(value("Qc Qd").category, HandRank::OnePair);
+        assert_eq!(value("Qc Qd").tiebreak, vec![Rank::Queen]);
+        assert_eq!(value("Ah Kh").tiebreak, vec![Rank::Ace, Rank::King]);
+        assert!(value("Qc Qd") > value("Ah Kh"));
+        assert_eq!(value("7c 7d 7h 2s").category, HandRank::ThreeOfAKind);
+        assert_eq!(value("Jk 9h").category, HandRank::OnePair);
+        assert_eq!(value("Jk 9h").tiebreak, vec![Rank::Nine]);
+        // Four to a straight flush is still just high cards
+        assert_eq!(value("9h Th Jh Qh").category, HandRank::HighCard);
+    }
+}
+
+mod notation_tests {
+    use super::*;
+    use joker_poker::GameError;
+
+    #[test]
+    fn test_card_round_trip() {
+        for suit in Suit::ALL {
+            for rank in Rank::ALL {
+                let card = Card::new(rank, suit);
+                let parsed: Card = card.to_string().parse().unwrap();
+                assert_eq!(parsed, card);
+            }
+        }
+    }
+
+    #[test]
+    fn test_card_display() {
+        assert_eq!(Card::new(Rank::Ace, Suit::Hearts).to_string(), "Ah");
+        assert_eq!(Card::new(Rank::Ten, Suit::Clubs).to_string(), "Tc");
+        assert_eq!(format!("{:#}", Card::new(Rank::Ace, Suit::Hearts)), "A♥");
+        assert_eq!(Card::joker(0).to_string(), "Jk");
+        assert_eq!(Card::joker(3).to_string(), "Jk3");
+        assert_eq!(Card::joker(3).to_string().parse::<Card>().unwrap(), Card::joker(3));
+    }
+
+    #[test]
+    fn test_card_parse_variants() {
+        for notation in ["Ah", "ah", "AH", "A♥", "A♡"] {
+            let card: Card = notation.parse().unwrap();
+            assert_eq!(card, Card::new(Rank::Ace, Suit::Hearts), "{}", notation);
+        }
+        let ten: Card = "10d".parse().unwrap();
+        assert_eq!(ten.rank(), Some(Rank::Ten));
+        assert!("Jk".parse::<Card>().unwrap().is_joker());
+        assert!("JK".parse::<Card>().unwrap().is_joker());
+    }
+
+    #[test]
+    fn test_card_parse_errors() {
+        let message = |notation: &str| match notation.parse::<Card>() {
+            Err(GameError::InvalidCardNotation(message)) => message,
+            other => panic!("{} should not parse: {:?}", notation, other.map(|c| c.to_string())),
+        };
+        assert!(message("Xh").contains("unknown rank \"X\""));
+        assert!(message("Ax").contains("unknown suit 'x'"));
+        assert!(message("A").contains("needs a rank and a suit"));
+        assert!(message("").contains("empty"));
+        assert!(message("AAh").contains("unknown rank \"AA\""));
+    }
+
+    #[test]
+    fn test_hand_round_trip() {
+        let hand: Hand = "Ah Kd Tc 9s Jk".parse().unwrap();
+        assert_eq!(hand.cards.len(), 5);
+        assert_eq!(hand.to_string(), "Ah Kd Tc 9s Jk");
+        assert_eq!(format!("{:#}", hand), "A♥ K♦ T♣ 9♠ Jk");
+        assert_eq!(format!("{:#}", hand).parse::<Hand>().unwrap().to_string(), "Ah Kd Tc 9s Jk");
+    }
+
+    #[test]
+    fn test_hand_separators() {
+        let hand: Hand = " Ah,Kd,  Tc , 9s\tJk ".parse().unwrap();
+        assert_eq!(hand.to_string(), "Ah Kd Tc 9s Jk");
+        assert!("".parse::<Hand>().unwrap().cards.is_empty());
+    }
+
+    #[test]
+    fn test_hand_parse_error_names_the_card() {
+        let err = "Ah Kd 1c 9s Jk".parse::<Hand>().unwrap_err();
+        assert!(matches!(err, GameError::InvalidCardNotation(_)));
+        assert_eq!(err.to_string(), "Invalid card notation: card 3: unknown rank \"1\" in \"1c\" (use 2-9, T, J, Q, K or A)");
+    }
+    #[test]
+    fn test_hand_numbers_its_jokers() {
+        let hand: Hand = "Jk 7c Jk".parse().unwrap();
+        assert_eq!(hand.cards, [Card::joker(0), Card::new(Rank::Seven, Suit::Clubs), Card::joker(1)]);
+        let numbered: Hand = "Jk Jk3 jk".parse().unwrap();
+        assert_eq!(numbered.cards, [Card::joker(0), Card::joker(3), Card::joker(1)]);
+        assert!("Jk999".parse::<Card>().unwrap_err().to_string().contains("unknown joker"));
+
+        // A bare joker skips the ids given explicitly anywhere in the hand
+        let skipped: Hand = "Jk Jk0 Jk".parse().unwrap();
+        assert_eq!(skipped.cards, [Card::joker(1), Card::joker(0), Card::joker(2)]);
+        assert_eq!(skipped.to_string().parse::<Hand>().unwrap().cards, skipped.cards);
+    }
+
+    #[test]
+    fn test_joker_ids_fit_a_deck() {
+        assert_eq!("Jk11".parse::<Card>().unwrap(), Card::joker(11));
+        let err = "Jk12".parse::<Card>().unwrap_err();
+        assert!(err.to_string().contains("use Jk or Jk0 to Jk11"));
+        assert!("Ah Jk20".parse::<Hand>().is_err());
+
+        let twelve = ["Jk"; 12].join(" ");
+        assert_eq!(twelve.parse::<Hand>().unwrap().cards.last(), Some(&Card::joker(11)));
+        let thirteen = ["Jk"; 13].join(" ");
+        assert!(matches!(thirteen.parse::<Hand>(), Err(GameError::InvalidJokerCount(_))));
+    }
+}
+
+mod card_identity_tests {
+    use super::*;
+    use joker_poker::JokerId;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_jokers_have_no_rank_or_suit() {
+        let joker = Card::joker(1);
+        assert_eq!(joker, Card::Joker(JokerId(1)));
+        assert_eq!((joker.rank(), joker.suit()), (None, None));
+        assert_eq!(format!("{:?}", joker), "Joker #1");
+    }
+
+    #[test]
+    fn test_cards_compare_by_identity() {
+        assert_ne!(Card::joker(0), Card::joker(1));
+        assert_ne!(Card::new(Rank::Ace, Suit::Hearts), Card::new(Rank::Ace, Suit::Spades));
+        let set: HashSet<Card> = [Card::joker(0), Card::joker(0), Card::new(Rank::Two, Suit::Clubs)].into();
+        assert_eq!(set.len(), 2);
+    }
+
+    #[test]
+    fn test_card_order() {
+        let mut cards = hand("Jk Ks 2c Kh Jk").cards;
+        cards.sort();
+        assert_eq!(cards, hand("2c Kh Ks Jk Jk").cards);
+    }
+}
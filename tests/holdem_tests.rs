@@ -0,0 +1,155 @@
+use joker_poker::game::GameState;
+use joker_poker::variant;
+use joker_poker::{Action, ForcedBets, GameEvent, HandRank, HoldEm, Variant};
+
+mod common;
+use common::*;
+
+mod dealing_tests {
+    use super::*;
+
+    #[test]
+    fn test_two_hole_cards_each() {
+        let mut game = stacked_game(HoldEm, 3, "As Ah Kd Kc Qd Qc");
+        game.begin_round().unwrap();
+        for (player, cards) in game.get_players().iter().zip(["As Ah", "Kd Kc", "Qd Qc"]) {
+            assert_eq!(player.hand.as_ref().unwrap().cards, hand(cards).cards);
+        }
+        assert!(game.get_board().is_empty());
+        assert_eq!(*game.get_state(), GameState::Betting);
+    }
+
+    #[test]
+    fn test_flop_turn_and_river() {
+        let mut game = stacked_game(HoldEm, 2, "As Ah Kd Kc 2c 7d 9h Ts 3s");
+        let mut events = game.begin_round().unwrap();
+        events.extend(play_passively(&mut game));
+
+        let boards: Vec<Vec<_>> = events.iter()
+            .filter_map(|event| match event {
+                GameEvent::BoardDealt { cards } => Some(cards.clone()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(boards, [hand("2c 7d 9h").cards, hand("Ts").cards, hand("3s").cards]);
+        assert_eq!(game.get_board(), &hand("2c 7d 9h Ts 3s").cards[..]);
+    }
+
+    #[test]
+    fn test_four_betting_streets() {
+        let mut game = stacked_game(HoldEm, 2, "As Ah Kd Kc 2c 7d 9h Ts 3s");
+        let mut events = game.begin_round().unwrap();
+        events.extend(play_passively(&mut game));
+
+        let streets: Vec<bool> = events.iter()
+            .filter_map(|event| match *event {
+                GameEvent::BettingStarted { final_round } => Some(final_round),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(streets, [false, false, false, true]);
+    }
+
+    #[test]
+    fn test_known_by_name() {
+        assert_eq!(variant::by_name("holdem").unwrap().name(), HoldEm.name());
+    }
+}
+
+mod betting_tests {
+    use super::*;
+
+    #[test]
+    fn test_later_streets_open_at_zero_after_the_button() {
+        let mut game = stacked_game(HoldEm, 3, "As Ah Kd Kc Qd Qc 2c 7d 9h Ts 3s");
+        game.set_forced_bets(ForcedBets { small_blind: 5, big_blind: 10, ..ForcedBets::default() }).unwrap();
+        game.begin_round().unwrap();
+        assert_eq!(game.get_current_player(), 2);
+
+        game.apply(2, Action::Call).unwrap();
+        game.apply(0, Action::Call).unwrap();
+        game.apply(1, Action::Check).unwrap();
+        assert_eq!(game.get_board().len(), 3);
+        assert_eq!(game.get_current_bet(), 0);
+        assert_eq!(game.get_current_player(), 0);
+        assert!(game.legal_actions().contains(&Action::Check));
+    }
+
+    #[test]
+    fn test_fold_before_the_flop_ends_the_round() {
+        let mut game = stacked_game(HoldEm, 2, "As Ah Kd Kc");
+        game.begin_round().unwrap();
+        game.apply(0, Action::Call).unwrap();
+        game.apply(1, Action::Raise(20)).unwrap();
+        game.apply(0, Action::Fold).unwrap();
+        assert!(game.is_game_over());
+        assert!(game.get_board().is_empty());
+        assert_eq!(game.get_players()[1].chips, 1010);
+    }
+
+    #[test]
+    fn test_raises_must_match_the_last_raise() {
+        let mut game = stacked_game(HoldEm, 3, "As Ah Kd Kc Qd Qc");
+        game.set_forced_bets(ForcedBets { small_blind: 10, big_blind: 20, ..ForcedBets::default() }).unwrap();
+        game.get_players_mut()[0].chips = 130;
+        game.begin_round().unwrap();
+        assert_eq!(game.get_current_player(), 2);
+        assert_eq!(game.get_min_raise(), 40);
+
+        game.apply(2, Action::Raise(100)).unwrap();
+        assert_eq!(game.get_min_raise(), 180);
+        // All-in for 130 is short of the 80 the last raise added
+        game.apply(0, Action::AllIn).unwrap();
+        game.apply(1, Action::Call).unwrap();
+        assert_eq!(game.legal_actions(), vec![Action::Fold, Action::Call]);
+        assert!(game.apply(2, Action::Raise(160)).is_err());
+    }
+
+    #[test]
+    fn test_all_in_runs_out_the_board() {
+        let mut game = stacked_game(HoldEm, 2, "Kd Kc As Ah 2c 7d 9h Ts Ks");
+        game.begin_round().unwrap();
+        game.apply(0, Action::AllIn).unwrap();
+        game.apply(1, Action::Call).unwrap();
+        assert!(game.is_game_over());
+        assert_eq!(game.get_board().len(), 5);
+        // Kings make a set on the river
+        assert_eq!(game.get_players()[0].chips, 2000);
+    }
+}
+
+mod evaluation_tests {
+    use super::*;
+
+    #[test]
+    fn test_best_five_of_seven() {
+        let value = HoldEm.value(&hand("2c 3d"), &hand("Ah Kh Qh Jh Th").cards);
+        assert_eq!(value.category, HandRank::RoyalFlush);
+
+        let evaluation = HoldEm.evaluate(&hand("As 9c"), &hand("Ah Kd 9h 4s 2d").cards);
+        assert_eq!(evaluation.value.category, HandRank::TwoPair);
+        assert_eq!(evaluation.cards.len(), 5);
+    }
+
+    #[test]
+    fn test_jokers_are_wild() {
+        let evaluation = HoldEm.evaluate(&hand("Jk 2c"), &hand("Ah Kh Qh Jh 3d").cards);
+        assert_eq!(evaluation.value.category, HandRank::RoyalFlush);
+        assert_eq!(evaluation.jokers.len(), 1);
+        assert_eq!(evaluation.jokers[0].index, 0);
+
+        // A joker on the board plays for everyone
+        let board = hand("Jk 7c 7d 2s 9h").cards;
+        assert_eq!(HoldEm.value(&hand("7h Ks"), &board).category, HandRank::FourOfAKind);
+        assert_eq!(HoldEm.value(&hand("As Kd"), &board).category, HandRank::ThreeOfAKind);
+    }
+
+    #[test]
+    fn test_showdown_uses_the_board() {
+        // Player 1's pair of aces loses to player 2's trips made with the board
+        let mut game = stacked_game(HoldEm, 2, "As Ad 9c 9d 9h 2c 5d Ts Kh");
+        let mut events = game.begin_round().unwrap();
+        events.extend(play_passively(&mut game));
+        assert!(events.iter().any(|e| matches!(e, GameEvent::PotAwarded { player: 1, amount: 20, .. })));
+    }
+}
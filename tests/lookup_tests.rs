@@ -0,0 +1,89 @@
+#![cfg(feature = "lookup")]
+
+use joker_poker::lookup::{self, JOKER};
+use joker_poker::{Card, Hand, HandRank, Rank, Suit};
+
+// Calls `check` with every combination of `size` cards out of the 52 naturals
+fn for_each_combination(size: usize, mut check: impl FnMut(&[u8])) {
+    let mut indices: Vec<u8> = (0..size as u8).collect();
+    loop {
+        check(&indices);
+        let Some(i) = (0..size).rev().find(|&i| indices[i] < (52 - size + i) as u8) else {
+            return;
+        };
+        indices[i] += 1;
+        for j in i + 1..size {
+            indices[j] = indices[j - 1] + 1;
+        }
+    }
+}
+
+#[test]
+fn test_encode_round_trip() {
+    for code in 0..52 {
+        assert_eq!(lookup::encode(&lookup::decode(code)), code);
+    }
+    let joker = Card::joker(0);
+    assert_eq!(lookup::encode(&joker), JOKER);
+}
+
+#[test]
+fn test_matches_hand_evaluation_for_every_five_card_hand() {
+    for jokers in 0..=5 {
+        let mut checked = 0;
+        for_each_combination(5 - jokers, |naturals| {
+            let mut codes = naturals.to_vec();
+            codes.extend(std::iter::repeat_n(JOKER, jokers));
+            let hand = Hand::new(codes.iter().map(|&code| lookup::decode(code)).collect());
+            assert_eq!(
+                lookup::unpack(lookup::evaluate_codes(&codes)),
+                hand.evaluation().value,
+                "mismatch for {:?}",
+                hand.cards
+            );
+            checked += 1;
+        });
+        assert!(checked > 0);
+    }
+}
+
+#[test]
+fn test_packed_values_order_like_hand_values() {
+    let pair_of_aces = [48, 49, 20, 12, 4];
+    let pair_of_twos = [0, 1, 44, 40, 36];
+    assert!(lookup::evaluate_codes(&pair_of_aces) > lookup::evaluate_codes(&pair_of_twos));
+}
+
+#[test]
+fn test_hand_value_uses_lookup() {
+    let hand = Hand::new(vec![
+        Card::new(Rank::Ace, Suit::Spades),
+        Card::new(Rank::King, Suit::Spades),
+        Card::new(Rank::Queen, Suit::Spades),
+        Card::new(Rank::Jack, Suit::Spades),
+        Card::joker(0),
+        Card::new(Rank::Two, Suit::Clubs),
+        Card::new(Rank::Two, Suit::Hearts),
+    ]);
+    assert_eq!(hand.value().category, HandRank::RoyalFlush);
+    assert_eq!(hand.value(), hand.evaluation().value);
+}
+
+#[test]
+fn test_best_of_seven_matches_hand_evaluation() {
+    use rand::rngs::StdRng;
+    use rand::seq::SliceRandom;
+    use rand::SeedableRng;
+
+    let mut rng = StdRng::seed_from_u64(11);
+    let mut deck: Vec<u8> = (0..52).chain([JOKER, JOKER]).collect();
+    for _ in 0..2000 {
+        deck.shuffle(&mut rng);
+        let hand = Hand::new(deck[..7].iter().map(|&code| lookup::decode(code)).collect());
+        assert_eq!(lookup::evaluate_codes(&deck[..7]), lookup::evaluate_packed(&hand.cards));
+        assert_eq!(lookup::evaluate(&hand.cards), hand.evaluation().value, "mismatch for {:?}", hand.cards);
+    }
+    // Past nine cards the codes no longer fit on the stack but score the same
+    let ten: Vec<Card> = deck[..10].iter().map(|&code| lookup::decode(code)).collect();
+    assert_eq!(lookup::evaluate_packed(&ten), lookup::evaluate_codes(&deck[..10]));
+}
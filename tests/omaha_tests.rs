@@ -0,0 +1,107 @@
+use joker_poker::error::GameError;
+use joker_poker::variant;
+use joker_poker::{Action, BetLimit, GameEvent, HandRank, Omaha, Rank, Variant};
+
+mod common;
+use common::*;
+
+mod dealing_tests {
+    use super::*;
+
+    #[test]
+    fn test_four_hole_cards_each() {
+        let mut game = stacked_game(Omaha::new(BetLimit::NoLimit), 2, "As Ah Kd Kc Qd Qc Jd Jc");
+        game.begin_round().unwrap();
+        assert_eq!(game.get_players()[0].hand.as_ref().unwrap().cards, hand("As Ah Kd Kc").cards);
+        assert_eq!(game.get_players()[1].hand.as_ref().unwrap().cards, hand("Qd Qc Jd Jc").cards);
+    }
+
+    #[test]
+    fn test_known_by_name() {
+        assert_eq!(variant::by_name("omaha").unwrap().bet_limit(), BetLimit::NoLimit);
+        assert_eq!(variant::by_name("pot-limit-omaha").unwrap().bet_limit(), BetLimit::PotLimit);
+        assert_eq!(Omaha::default().name(), "omaha");
+        assert_eq!(Omaha::new(BetLimit::PotLimit).name(), "pot-limit-omaha");
+    }
+}
+
+mod evaluation_tests {
+    use super::*;
+
+    #[test]
+    fn test_exactly_two_hole_cards() {
+        // Four hole spades make no flush with only two of them played
+        let board = hand("Ah 8d 5s 3c Kc").cards;
+        let value = Omaha::default().value(&hand("Qs Js Ts 9s"), &board);
+        assert_eq!(value.category, HandRank::HighCard);
+
+        // Nor does a straight on the board play by itself
+        let board = hand("9h Th Jc Qd Ks").cards;
+        let value = Omaha::default().value(&hand("2c 2d 4s 7h"), &board);
+        assert_eq!(value.category, HandRank::OnePair);
+    }
+
+    #[test]
+    fn test_exactly_three_board_cards() {
+        // Trips in the hole only count as a pair
+        let board = hand("2c 5d 8h Jc Ks").cards;
+        let value = Omaha::default().value(&hand("Ac Ad Ah 7s"), &board);
+        assert_eq!(value.category, HandRank::OnePair);
+        assert_eq!(value.tiebreak, vec![Rank::Ace, Rank::King, Rank::Jack, Rank::Eight]);
+    }
+
+    #[test]
+    fn test_jokers_are_wild() {
+        let evaluation = Omaha::default().evaluate(&hand("Jk 9h 2c 3d"), &hand("Ah Kh Qh 4s 5c").cards);
+        assert_eq!(evaluation.value.category, HandRank::Flush);
+        assert_eq!(evaluation.jokers[0].index, 0);
+
+        // A joker on the board counts as one of the three
+        let evaluation = Omaha::default().evaluate(&hand("7c 7d Kd 2s"), &hand("Jk 7h 3c 9d 4h").cards);
+        assert_eq!(evaluation.value.category, HandRank::FourOfAKind);
+        assert_eq!(evaluation.jokers[0].index, 4);
+    }
+
+    #[test]
+    fn test_showdown_uses_two_hole_cards() {
+        // Player 1's hearts make a flush with the board in Hold'em but only
+        // ace high here. Player 2 plays the pair of kings; kings and deuces
+        // would take a third hole card, the 2s.
+        let mut game = stacked_game(Omaha::new(BetLimit::NoLimit), 2, "Ah Qh Jh 3h Kc Kd 2s 3s 2h 7c 8h Ts 4c");
+        let mut events = game.begin_round().unwrap();
+        while !game.is_game_over() {
+            let player = game.get_current_player();
+            let action = if game.get_amount_to_call(player) == 0 { Action::Check } else { Action::Call };
+            events.extend(game.apply(player, action).unwrap());
+        }
+        assert!(events.iter().any(|e| matches!(e, GameEvent::PotAwarded { player: 1, amount: 20, .. })));
+        let categories: Vec<HandRank> = events.iter()
+            .filter_map(|e| match e {
+                GameEvent::HandShown { evaluation, .. } => Some(evaluation.value.category),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(categories, [HandRank::HighCard, HandRank::OnePair]);
+    }
+}
+
+mod pot_limit_tests {
+    use super::*;
+
+    #[test]
+    fn test_raises_are_capped_at_the_pot() {
+        let mut game = stacked_game(Omaha::new(BetLimit::PotLimit), 2, "As Ah Kd Kc Qd Qc Jd Jc");
+        game.begin_round().unwrap();
+        assert_eq!(game.get_max_bet(0), 20);
+        assert!(!game.legal_actions().contains(&Action::AllIn));
+        assert!(matches!(game.apply(0, Action::Raise(40)), Err(GameError::BetOutOfRange { max: 20, .. })));
+    }
+
+    #[test]
+    fn test_no_limit_allows_all_in() {
+        let mut game = stacked_game(Omaha::new(BetLimit::NoLimit), 2, "As Ah Kd Kc Qd Qc Jd Jc");
+        game.begin_round().unwrap();
+        assert_eq!(game.get_max_bet(0), 1000);
+        assert!(game.legal_actions().contains(&Action::AllIn));
+    }
+}
@@ -1,4 +1,4 @@
-use joker_poker::{Player, Hand, Card, Rank, Suit};
+use joker_poker::{GameError, Player, Hand, Card, Rank, Suit};
 
 fn create_test_player(name: &str, chips: u32) -> Player {
     Player::new(name.to_string(), chips)
@@ -6,11 +6,11 @@ fn create_test_player(name: &str, chips: u32) -> Player {
 
 fn create_test_hand() -> Hand {
     let cards = vec![
-        Card::new(Rank::Ace, Suit::Hearts, false),
-        Card::new(Rank::King, Suit::Hearts, false),
-        Card::new(Rank::Queen, Suit::Hearts, false),
-        Card::new(Rank::Jack, Suit::Hearts, false),
-        Card::new(Rank::Ten, Suit::Hearts, false),
+        Card::new(Rank::Ace, Suit::Hearts),
+        Card::new(Rank::King, Suit::Hearts),
+        Card::new(Rank::Queen, Suit::Hearts),
+        Card::new(Rank::Jack, Suit::Hearts),
+        Card::new(Rank::Ten, Suit::Hearts),
     ];
     Hand::new(cards)
 }
@@ -57,7 +57,8 @@ mod chip_management_tests {
     fn test_insufficient_chips_bet() {
         let mut player = create_test_player("PoorPlayer", 100);
         let bet_result = player.place_bet(500);
-        assert!(bet_result.is_err());
+        assert!(matches!(bet_result, Err(GameError::InsufficientChips { needed: 500, available: 100 })));
+        assert!(bet_result.unwrap_err().can_retry());
         assert_eq!(player.chips, 100); // Chips shouldn't change on failed bet
     }
 
@@ -114,11 +115,11 @@ mod hand_management_tests {
         player.hand = Some(initial_hand);
         
         let new_hand = Hand::new(vec![
-            Card::new(Rank::Two, Suit::Hearts, false),
-            Card::new(Rank::Three, Suit::Hearts, false),
-            Card::new(Rank::Four, Suit::Hearts, false),
-            Card::new(Rank::Five, Suit::Hearts, false),
-            Card::new(Rank::Six, Suit::Hearts, false),
+            Card::new(Rank::Two, Suit::Hearts),
+            Card::new(Rank::Three, Suit::Hearts),
+            Card::new(Rank::Four, Suit::Hearts),
+            Card::new(Rank::Five, Suit::Hearts),
+            Card::new(Rank::Six, Suit::Hearts),
         ]);
         player.hand = Some(new_hand);
         
@@ -211,6 +212,11 @@ mod chip_modification_tests {
         let mut player = create_test_player("ChipRemovePlayer", 1000);
         assert!(player.remove_chips(500).is_ok());
         assert_eq!(player.chips, 500);
+        assert!(matches!(
+            player.remove_chips(600),
+            Err(GameError::InsufficientChips { needed: 600, available: 500 })
+        ));
+        assert_eq!(player.chips, 500);
     }
 
     #[test]
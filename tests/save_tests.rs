@@ -0,0 +1,302 @@
+#![cfg(feature = "serde")]
+
+use joker_poker::error::GameError;
+use joker_poker::game::{GameState, SAVE_VERSION};
+use joker_poker::{Action, Card, Deck, ForcedBets, Game, Hand, HandEvaluation, HoldEm, JokerDraw};
+use joker_poker::{PlayerController, Step, TableView, Variant};
+use std::path::PathBuf;
+
+// A file in the temp directory that is removed again when dropped
+struct TempSave(PathBuf);
+
+impl TempSave {
+    fn new(name: &str) -> Self {
+        TempSave(std::env::temp_dir().join(format!("joker_poker_{}_{}.json", name, std::process::id())))
+    }
+}
+
+impl Drop for TempSave {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.0);
+    }
+}
+
+fn seeded_game(seed: u64) -> Game {
+    let names = vec!["Alice".to_string(), "Bob".to_string(), "Carol".to_string()];
+    let mut game = Game::new(names, 1000, 10, 2).unwrap();
+    game.set_seed(seed);
+    game
+}
+
+// Raises now and then and draws a different number of cards each time, so
+// that chips move and the deck is used
+fn play_to_end(game: &mut Game) -> Vec<String> {
+    let mut log = Vec::new();
+    let mut step = 0;
+    while !game.is_game_over() {
+        let actions = game.legal_actions();
+        let action = match game.get_state() {
+            GameState::Drawing => actions[step % actions.len()].clone(),
+            _ if step % 4 == 0 && actions.len() == 4 => actions[2].clone(),
+            _ => actions[1].clone(),
+        };
+        game.apply(game.get_current_player(), action).unwrap();
+        log.push(snapshot(game));
+        step += 1;
+    }
+    log
+}
+
+fn snapshot(game: &Game) -> String {
+    let players: Vec<String> = game.get_players().iter()
+        .map(|p| format!("{} {} {:?} {} {}/{}", p.name, p.chips, p.hand, p.in_round, p.wins, p.hands_played))
+        .collect();
+    format!("{:?} pot {} dealer {} {:?}", game.get_state(), game.get_pot(), game.get_dealer(), players)
+}
+
+mod between_rounds_tests {
+    use super::*;
+
+    #[test]
+    fn test_save_keeps_players_and_settings() {
+        let save = TempSave::new("settings");
+        let mut game = seeded_game(5);
+        let forced_bets = ForcedBets { ante: 1, small_blind: 5, big_blind: 10, bring_in: 0 };
+        game.set_forced_bets(forced_bets).unwrap();
+        game.begin_round().unwrap();
+        play_to_end(&mut game);
+        game.save(&save.0).unwrap();
+
+        let loaded = Game::load(&save.0).unwrap();
+        assert_eq!(snapshot(&loaded), snapshot(&game));
+        assert_eq!(loaded.get_min_bet(), 10);
+        assert_eq!(loaded.get_seed(), 5);
+        assert_eq!(loaded.get_dealer(), 0);
+        assert_eq!(loaded.get_forced_bets(), forced_bets);
+        assert_eq!(loaded.get_deck().len(), game.get_deck().len());
+    }
+
+    #[test]
+    fn test_later_rounds_are_dealt_the_same() {
+        let save = TempSave::new("later_rounds");
+        let mut game = seeded_game(11);
+        game.begin_round().unwrap();
+        play_to_end(&mut game);
+        game.save(&save.0).unwrap();
+        let mut loaded = Game::load(&save.0).unwrap();
+
+        for _ in 0..3 {
+            game.begin_round().unwrap();
+            loaded.begin_round().unwrap();
+            assert_eq!(loaded.get_round_seed(), game.get_round_seed());
+            assert_eq!(play_to_end(&mut loaded), play_to_end(&mut game));
+        }
+    }
+
+    #[test]
+    fn test_save_replaces_the_old_file() {
+        let save = TempSave::new("replace");
+        let mut game = seeded_game(7);
+        game.save(&save.0).unwrap();
+        game.begin_round().unwrap();
+        play_to_end(&mut game);
+        game.save(&save.0).unwrap();
+
+        let loaded = Game::load(&save.0).unwrap();
+        assert_eq!(snapshot(&loaded), snapshot(&game));
+        let mut temp = save.0.clone().into_os_string();
+        temp.push(".tmp");
+        assert!(!PathBuf::from(temp).exists());
+    }
+}
+
+mod mid_round_tests {
+    use super::*;
+
+    #[test]
+    fn test_round_continues_after_load() {
+        let save = TempSave::new("mid_round");
+        let mut game = seeded_game(23);
+        game.begin_round().unwrap();
+        game.apply(0, Action::Raise(30)).unwrap();
+        game.apply(1, Action::Call).unwrap();
+        game.save(&save.0).unwrap();
+
+        let mut loaded = Game::load(&save.0).unwrap();
+        assert!(loaded.is_round_active());
+        assert_eq!(loaded.get_current_player(), 2);
+        assert_eq!(loaded.get_current_bet(), 30);
+        assert_eq!(loaded.legal_actions(), game.legal_actions());
+        assert_eq!(play_to_end(&mut loaded), play_to_end(&mut game));
+    }
+
+    #[test]
+    fn test_board_is_kept() {
+        let save = TempSave::new("board");
+        let mut game = seeded_game(5);
+        game.set_variant(Box::new(HoldEm)).unwrap();
+        game.begin_round().unwrap();
+        for player in [0, 1, 2] {
+            game.apply(player, Action::Call).unwrap();
+        }
+        assert_eq!(game.get_board().len(), 3);
+        game.save(&save.0).unwrap();
+
+        let mut loaded = Game::load(&save.0).unwrap();
+        assert_eq!(loaded.get_variant().name(), "holdem");
+        assert_eq!(loaded.get_board(), game.get_board());
+        assert_eq!(play_to_end(&mut loaded), play_to_end(&mut game));
+        assert_eq!(loaded.get_board(), game.get_board());
+    }
+
+    #[test]
+    fn test_resume_round_plays_out_a_loaded_round() {
+        struct Passive;
+
+        impl PlayerController for Passive {
+            fn choose_action(&mut self, view: &TableView) -> Action {
+                if view.to_call == 0 { Action::Check } else { Action::Call }
+            }
+
+            fn choose_discards(&mut self, _view: &TableView) -> Vec<usize> {
+                vec![0]
+            }
+        }
+
+        let save = TempSave::new("resume_round");
+        let mut game = seeded_game(2);
+        game.begin_round().unwrap();
+        game.save(&save.0).unwrap();
+
+        let mut loaded = Game::load(&save.0).unwrap();
+        let mut controllers: Vec<Box<dyn PlayerController>> = (0..3).map(|_| Box::new(Passive) as Box<dyn PlayerController>).collect();
+        let mut events = Vec::new();
+        loaded.resume_round(&mut controllers, &mut events).unwrap();
+        assert!(loaded.is_game_over());
+        assert!(!events.is_empty());
+        let chips: u32 = loaded.get_players().iter().map(|p| p.chips).sum();
+        assert_eq!(chips, 3000);
+    }
+
+    #[test]
+    fn test_saved_deck_reshuffles_the_same() {
+        let mut deck = Deck::with_seed(1, 8);
+        deck.shuffle();
+        let hand = deck.deal(20).unwrap();
+        deck.discard(hand.cards);
+
+        let json = serde_json::to_string(&deck).unwrap();
+        let mut copy: Deck = serde_json::from_str(&json).unwrap();
+        deck.reshuffle_discards();
+        copy.reshuffle_discards();
+        assert_eq!(copy.remaining(), deck.remaining());
+        assert_eq!(copy.deal(53).unwrap().cards, deck.deal(53).unwrap().cards);
+    }
+}
+
+mod file_format_tests {
+    use super::*;
+
+    #[test]
+    fn test_file_is_versioned() {
+        let save = TempSave::new("versioned");
+        seeded_game(1).save(&save.0).unwrap();
+        let json = std::fs::read_to_string(&save.0).unwrap();
+        assert!(json.starts_with(&format!("{{\"version\":{},", SAVE_VERSION)));
+    }
+
+    #[test]
+    fn test_rejects_other_versions() {
+        let save = TempSave::new("future");
+        seeded_game(1).save(&save.0).unwrap();
+        let json = std::fs::read_to_string(&save.0).unwrap()
+            .replacen(&format!("\"version\":{}", SAVE_VERSION), "\"version\":99", 1);
+        std::fs::write(&save.0, json).unwrap();
+        assert!(matches!(
+            Game::load(&save.0),
+            Err(GameError::UnsupportedSaveVersion { found: 99, supported: SAVE_VERSION })
+        ));
+    }
+
+    #[test]
+    fn test_variant_is_saved_by_name() {
+        let save = TempSave::new("variant");
+        seeded_game(1).save(&save.0).unwrap();
+        let json = std::fs::read_to_string(&save.0).unwrap();
+        assert!(json.contains("\"variant\":\"joker-draw\""));
+        assert_eq!(Game::load(&save.0).unwrap().get_variant().name(), "joker-draw");
+
+        std::fs::write(&save.0, json.replacen("\"joker-draw\"", "\"no-such-game\"", 1)).unwrap();
+        assert!(matches!(Game::load(&save.0), Err(GameError::InvalidSaveFile(_))));
+    }
+
+    #[test]
+    fn test_refuses_to_save_unknown_variants() {
+        // Plays like the default game, under a name `by_name` doesn't know
+        struct HouseRules;
+
+        impl Variant for HouseRules {
+            fn name(&self) -> &'static str {
+                "house-rules"
+            }
+
+            fn steps(&self) -> Vec<Step> {
+                JokerDraw.steps()
+            }
+
+            fn evaluate(&self, hole: &Hand, board: &[Card]) -> HandEvaluation {
+                JokerDraw.evaluate(hole, board)
+            }
+        }
+
+        let save = TempSave::new("house_rules");
+        let mut game = seeded_game(1);
+        game.set_variant(Box::new(HouseRules)).unwrap();
+        assert!(matches!(game.save(&save.0), Err(GameError::InvalidSaveFile(_))));
+        assert!(!save.0.exists());
+    }
+
+    #[test]
+    fn test_rejects_too_many_jokers() {
+        let save = TempSave::new("jokers");
+        seeded_game(1).save(&save.0).unwrap();
+        let json = std::fs::read_to_string(&save.0).unwrap().replacen("\"num_jokers\":2", "\"num_jokers\":20", 1);
+        std::fs::write(&save.0, json).unwrap();
+        assert!(matches!(Game::load(&save.0), Err(GameError::InvalidSaveFile(_))));
+    }
+
+    #[test]
+    fn test_rejects_seats_that_dont_match_the_players() {
+        let save = TempSave::new("seats");
+        let mut game = seeded_game(1);
+        game.begin_round().unwrap();
+        game.save(&save.0).unwrap();
+        let json = std::fs::read_to_string(&save.0).unwrap();
+
+        let edits = [
+            ("\"dealer\":2", "\"dealer\":3"),
+            ("\"current_player\":0", "\"current_player\":7"),
+            ("\"street_bets\":[", "\"street_bets\":[0,"),
+            ("\"to_act\":[", "\"to_act\":[true,"),
+            ("\"may_raise\":[true,", "\"may_raise\":["),
+            ("\"round_bets\":[", "\"round_bets\":[0,"),
+        ];
+        for (from, to) in edits {
+            assert!(json.contains(from), "{} not in the save", from);
+            std::fs::write(&save.0, json.replacen(from, to, 1)).unwrap();
+            assert!(matches!(Game::load(&save.0), Err(GameError::InvalidSaveFile(_))), "loaded with {}", to);
+        }
+    }
+
+    #[test]
+    fn test_rejects_bad_files() {
+        let save = TempSave::new("garbage");
+        std::fs::write(&save.0, "not a save file").unwrap();
+        assert!(matches!(Game::load(&save.0), Err(GameError::InvalidSaveFile(_))));
+        std::fs::write(&save.0, format!("{{\"version\":{}}}", SAVE_VERSION)).unwrap();
+        assert!(matches!(Game::load(&save.0), Err(GameError::InvalidSaveFile(_))));
+
+        let missing = TempSave::new("missing");
+        assert!(matches!(Game::load(&missing.0), Err(GameError::IoError(_))));
+    }
+}
@@ -0,0 +1,98 @@
+#![cfg(feature = "serde")]
+
+use joker_poker::game::GameState;
+use joker_poker::{Card, Hand, HandRank, HandValue, Player, Rank, Suit};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+fn round_trip<T: Serialize + DeserializeOwned>(value: &T) -> T {
+    let json = serde_json::to_string(value).unwrap();
+    serde_json::from_str(&json).unwrap()
+}
+
+mod card_tests {
+    use super::*;
+
+    #[test]
+    fn test_cards_serialize_as_notation() {
+        assert_eq!(serde_json::to_string(&Card::new(Rank::Ace, Suit::Hearts)).unwrap(), "\"Ah\"");
+        assert_eq!(serde_json::to_string(&Card::new(Rank::Ten, Suit::Clubs)).unwrap(), "\"Tc\"");
+        assert_eq!(serde_json::to_string(&Card::joker(0)).unwrap(), "\"Jk\"");
+        assert_eq!(serde_json::to_string(&Card::joker(3)).unwrap(), "\"Jk3\"");
+    }
+
+    #[test]
+    fn test_card_round_trip() {
+        for card in joker_poker::CardSet::full(12) {
+            assert_eq!(round_trip(&card), card);
+        }
+    }
+
+    #[test]
+    fn test_bad_card_is_an_error() {
+        assert!(serde_json::from_str::<Card>("\"Xh\"").is_err());
+        assert!(serde_json::from_str::<Card>("\"Jkx\"").is_err());
+        assert!(serde_json::from_str::<Card>("12").is_err());
+    }
+
+    #[test]
+    fn test_rank_and_suit_round_trip() {
+        for rank in Rank::ALL {
+            assert_eq!(round_trip(&rank), rank);
+        }
+        for suit in Suit::ALL {
+            assert_eq!(round_trip(&suit), suit);
+        }
+    }
+}
+
+mod hand_tests {
+    use super::*;
+
+    #[test]
+    fn test_hand_round_trip() {
+        let hand: Hand = "Ah Kd Jk Tc Jk".parse().unwrap();
+        assert_eq!(serde_json::to_string(&hand).unwrap(), r#"{"cards":["Ah","Kd","Jk","Tc","Jk1"]}"#);
+        assert_eq!(round_trip(&hand).cards, hand.cards);
+    }
+
+    #[test]
+    fn test_hand_keeps_face_up_cards() {
+        let mut hand: Hand = "Ah Kd".parse().unwrap();
+        hand.add_face_up("Tc".parse::<Hand>().unwrap().cards);
+        assert_eq!(serde_json::to_string(&hand).unwrap(), r#"{"cards":["Ah","Kd","Tc"],"face_up":[false,false,true]}"#);
+        let copy = round_trip(&hand);
+        assert_eq!(copy.visible().cards, hand.visible().cards);
+    }
+
+    #[test]
+    fn test_hand_rank_and_value_round_trip() {
+        assert_eq!(round_trip(&HandRank::FiveOfAKind), HandRank::FiveOfAKind);
+        let value: HandValue = "Kh Kd 7c 7s 2h".parse::<Hand>().unwrap().value();
+        assert_eq!(round_trip(&value), value);
+    }
+}
+
+mod player_tests {
+    use super::*;
+
+    #[test]
+    fn test_player_round_trip() {
+        let mut player = Player::new("Alice".to_string(), 750);
+        player.hand = Some("9s 9h Jk 4c 2d".parse().unwrap());
+        player.add_win();
+        let copy = round_trip(&player);
+        assert_eq!(copy.name, "Alice");
+        assert_eq!(copy.chips, 750);
+        assert_eq!(copy.wins, 1);
+        assert_eq!(copy.in_round, player.in_round);
+        assert_eq!(copy.hand.unwrap().cards, player.hand.unwrap().cards);
+    }
+
+    #[test]
+    fn test_game_state_round_trip() {
+        for state in [GameState::Betting, GameState::Drawing, GameState::FinalBetting, GameState::ShowDown, GameState::GameOver] {
+            assert_eq!(round_trip(&state), state);
+        }
+    }
+}
@@ -0,0 +1,201 @@
+use joker_poker::game::GameState;
+use joker_poker::variant;
+use joker_poker::{Action, Deck, ForcedBet, ForcedBets, Game, GameEvent, HandRank, SevenCardStud, Variant};
+
+mod common;
+use common::*;
+
+fn bring_in(amount: u32) -> ForcedBets {
+    ForcedBets { bring_in: amount, ..ForcedBets::default() }
+}
+
+mod dealing_tests {
+    use super::*;
+
+    #[test]
+    fn test_two_down_and_one_up() {
+        let mut game = stacked_game(SevenCardStud, 2, "As Ah Kd Kc 9s 4h");
+        let events = game.begin_round().unwrap();
+        let first = game.get_players()[0].hand.as_ref().unwrap();
+        assert_eq!(first.cards, hand("As Ah 9s").cards);
+        assert_eq!(first.visible().cards, hand("9s").cards);
+        assert!(events.iter().any(|e| matches!(e, GameEvent::UpCardsDealt { player: 1, cards } if *cards == hand("4h").cards)));
+    }
+
+    #[test]
+    fn test_seven_cards_over_five_streets() {
+        let mut game = stacked_game(SevenCardStud, 2, "As Ah Kd Kc 9s 4h 2c 3c 5d 6d 7s 8s Jh Qh");
+        let mut events = game.begin_round().unwrap();
+        events.extend(play_passively(&mut game));
+
+        let streets = events.iter().filter(|e| matches!(e, GameEvent::BettingStarted { .. })).count();
+        assert_eq!(streets, 5);
+        let first = game.get_players()[0].hand.as_ref().unwrap();
+        assert_eq!(first.cards, hand("As Ah 9s 2c 5d 7s Jh").cards);
+        // The last card comes face down
+        assert_eq!(first.visible().cards, hand("9s 2c 5d 7s").cards);
+        assert!(game.get_board().is_empty());
+    }
+
+    #[test]
+    fn test_up_cards_are_public() {
+        let mut game = stacked_game(SevenCardStud, 2, "As Ah Kd Kc 9s 4h");
+        game.begin_round().unwrap();
+        let view = game.table_view(0);
+        assert_eq!(view.seats[0].up_cards, hand("9s").cards);
+        assert_eq!(view.seats[1].up_cards, hand("4h").cards);
+    }
+
+    #[test]
+    fn test_known_by_name() {
+        assert_eq!(variant::by_name("seven-card-stud").unwrap().name(), SevenCardStud.name());
+    }
+}
+
+mod action_order_tests {
+    use super::*;
+
+    #[test]
+    fn test_lowest_up_card_brings_in() {
+        let mut game = stacked_game(SevenCardStud, 3, "As Ah Kd Kc Qs Qh 9s 3h Tc");
+        game.set_forced_bets(bring_in(5)).unwrap();
+        let events = game.begin_round().unwrap();
+
+        let posts: Vec<(usize, ForcedBet)> = events.iter()
+            .filter_map(|event| match *event {
+                GameEvent::Posted { player, bet, .. } => Some((player, bet)),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(posts, [(1, ForcedBet::BringIn)]);
+        // Action carries on from the bring-in's left
+        assert_eq!(game.get_current_player(), 2);
+        assert_eq!(game.get_current_bet(), 5);
+    }
+
+    #[test]
+    fn test_bring_in_can_be_completed() {
+        let mut game = stacked_game(SevenCardStud, 3, "As Ah Kd Kc Qs Qh 9s 3h Tc");
+        game.set_forced_bets(bring_in(5)).unwrap();
+        game.begin_round().unwrap();
+        assert!(game.legal_actions().contains(&Action::Raise(10)));
+
+        // Completing to the minimum bet is a full raise, so the bring-in acts again
+        game.apply(2, Action::Raise(10)).unwrap();
+        assert_eq!(game.get_current_bet(), 10);
+        assert_eq!(game.get_min_raise(), 20);
+        assert!(game.apply(0, Action::Raise(15)).is_err());
+        game.apply(0, Action::Call).unwrap();
+        assert_eq!(game.get_current_player(), 1);
+        assert_eq!(game.get_amount_to_call(1), 5);
+    }
+
+    #[test]
+    fn test_bring_in_ties_go_by_suit() {
+        // Clubs are the lowest suit, then diamonds, hearts and spades
+        let mut game = stacked_game(SevenCardStud, 2, "As Ah Kd Kc 3c 3h");
+        game.set_forced_bets(bring_in(5)).unwrap();
+        game.begin_round().unwrap();
+        assert_eq!(game.get_current_player(), 1);
+        assert_eq!(game.get_street_bet(0), 5);
+
+        let mut game = stacked_game(SevenCardStud, 3, "As Ah Ad Ks Kh Kd 3s 3d 3h");
+        game.set_forced_bets(bring_in(5)).unwrap();
+        game.begin_round().unwrap();
+        assert_eq!(game.get_street_bet(1), 5);
+    }
+
+    #[test]
+    fn test_tied_deuces_bring_in_by_suit() {
+        let mut game = stacked_game(SevenCardStud, 2, "As Ah Kd Kc 2h 2c");
+        game.set_forced_bets(bring_in(5)).unwrap();
+        game.begin_round().unwrap();
+        assert_eq!(game.get_street_bet(1), 5);
+        assert_eq!(game.get_street_bet(0), 0);
+        assert_eq!(game.get_current_player(), 0);
+    }
+
+    #[test]
+    fn test_joker_showing_never_brings_in() {
+        let names = vec!["Player1".to_string(), "Player2".to_string()];
+        let mut game = Game::new(names, 1000, 10, 1).unwrap();
+        game.set_variant(Box::new(SevenCardStud)).unwrap();
+        game.set_stacked_deck(Deck::stacked(hand("As Ah Kd Kc Jk 2h").cards, 1).unwrap());
+        game.set_forced_bets(bring_in(5)).unwrap();
+        game.begin_round().unwrap();
+        assert_eq!(game.get_street_bet(1), 5);
+    }
+
+    #[test]
+    fn test_lowest_card_opens_without_a_bring_in() {
+        let mut game = stacked_game(SevenCardStud, 3, "As Ah Kd Kc Qs Qh 9s 3h Tc");
+        game.begin_round().unwrap();
+        assert_eq!(game.get_current_player(), 1);
+    }
+
+    #[test]
+    fn test_best_hand_showing_acts_first() {
+        // Fourth street shows 9s 2d, 3h 3c and Tc Ks: the pair of threes
+        let mut game = stacked_game(SevenCardStud, 3, "As Ah Kd Kc Qs Qh 9s 3h Tc 2d 3c Ks");
+        game.set_forced_bets(bring_in(5)).unwrap();
+        game.begin_round().unwrap();
+        // The bring-in has already acted once both others call
+        game.apply(2, Action::Call).unwrap();
+        game.apply(0, Action::Call).unwrap();
+
+        assert_eq!(game.get_players()[1].hand.as_ref().unwrap().visible_value().category, HandRank::OnePair);
+        assert_eq!(game.get_current_bet(), 0);
+        assert_eq!(game.get_current_player(), 1);
+        game.apply(1, Action::Check).unwrap();
+        assert_eq!(game.get_current_player(), 2);
+    }
+
+    #[test]
+    fn test_ties_for_best_showing_go_to_the_first_after_the_button() {
+        // Everyone shows an ace and a king on fourth street
+        let mut game = stacked_game(SevenCardStud, 3, "2s 2h 3d 3c 4s 4h As Ad Ah Kd Kc Kh");
+        game.begin_round().unwrap();
+        play_street(&mut game);
+        assert_eq!(*game.get_state(), GameState::Betting);
+        assert_eq!(game.get_current_player(), 0);
+
+        // With the button on seat 0, seat 1 goes first
+        let mut game = stacked_game(SevenCardStud, 3, "2s 2h 3d 3c 4s 4h As Ad Ah Kd Kc Kh");
+        game.set_dealer(0).unwrap();
+        game.begin_round().unwrap();
+        play_street(&mut game);
+        assert_eq!(game.get_current_player(), 1);
+    }
+
+    fn play_street(game: &mut Game) {
+        let dealt = game.get_players()[0].hand.as_ref().unwrap().cards.len();
+        while game.get_players()[0].hand.as_ref().unwrap().cards.len() == dealt {
+            let action = if game.get_amount_to_call(game.get_current_player()) == 0 { Action::Check } else { Action::Call };
+            game.apply(game.get_current_player(), action).unwrap();
+        }
+    }
+}
+
+mod showdown_tests {
+    use super::*;
+
+    #[test]
+    fn test_best_five_of_seven() {
+        let mut cards = hand("As Ah");
+        cards.add_face_up(hand("Ad 7c 2s Kh").cards);
+        cards.add_cards(hand("Ac").cards);
+        assert_eq!(SevenCardStud.value(&cards, &[]).category, HandRank::FourOfAKind);
+        assert_eq!(SevenCardStud.evaluate(&cards, &[]).cards.len(), 5);
+    }
+
+    #[test]
+    fn test_down_cards_count_at_showdown() {
+        // Player 2's trip nines are all face down: both hole cards and the
+        // river. They beat player 1's kings, where only Ks shows and the
+        // second king is the face-down river card.
+        let mut game = stacked_game(SevenCardStud, 2, "2c 3d 9h 9d Ks 4c As 5h Qs 7c Js 8d Kd 9c");
+        let mut events = game.begin_round().unwrap();
+        events.extend(play_passively(&mut game));
+        assert!(events.iter().any(|e| matches!(e, GameEvent::PotAwarded { player: 1, amount: 20, .. })));
+    }
+}